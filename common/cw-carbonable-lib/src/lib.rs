@@ -1,3 +1,6 @@
+use cosmwasm_std::Binary;
+use cw721::Expiration;
+use cw721_base::MintMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -25,3 +28,46 @@ pub struct Metadata {
 pub type Extension = Option<Metadata>;
 
 pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension>;
+
+/// Carbonable NFT contract execute messages
+///
+/// cw721 messages, plus the carbonable specific ones
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum NftExecuteMsg {
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    Mint(MintMsg<Extension>),
+    Burn {
+        token_id: String,
+    },
+
+    /// Replace the metadata of a minted token, minter only
+    UpdateMetadata {
+        token_id: String,
+        extension: Extension,
+    },
+}
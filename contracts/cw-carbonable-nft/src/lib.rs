@@ -1,6 +1,6 @@
-use cosmwasm_std::Empty;
+use cosmwasm_std::{DepsMut, Empty, MessageInfo, Response, StdError};
 pub use cw721_base::{ContractError, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
-use cw_carbonable_lib::Extension;
+use cw_carbonable_lib::{ExecuteMsg, Extension, NftExecuteMsg};
 
 pub type CarbonableMetadataContract<'a> = cw721_base::Cw721Contract<'a, Extension, Empty>;
 
@@ -15,7 +15,6 @@ pub mod entry {
     use cosmwasm_std::entry_point;
     use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
     use cw2::set_contract_version;
-    use cw_carbonable_lib::ExecuteMsg;
 
     // This is a simple type to let us handle empty extensions
 
@@ -39,8 +38,47 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: ExecuteMsg,
+        msg: NftExecuteMsg,
     ) -> Result<Response, ContractError> {
+        let msg = match msg {
+            NftExecuteMsg::UpdateMetadata {
+                token_id,
+                extension,
+            } => return update_metadata(deps, info, token_id, extension),
+            NftExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            } => ExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            },
+            NftExecuteMsg::SendNft {
+                contract,
+                token_id,
+                msg,
+            } => ExecuteMsg::SendNft {
+                contract,
+                token_id,
+                msg,
+            },
+            NftExecuteMsg::Approve {
+                spender,
+                token_id,
+                expires,
+            } => ExecuteMsg::Approve {
+                spender,
+                token_id,
+                expires,
+            },
+            NftExecuteMsg::Revoke { spender, token_id } => ExecuteMsg::Revoke { spender, token_id },
+            NftExecuteMsg::ApproveAll { operator, expires } => {
+                ExecuteMsg::ApproveAll { operator, expires }
+            }
+            NftExecuteMsg::RevokeAll { operator } => ExecuteMsg::RevokeAll { operator },
+            NftExecuteMsg::Mint(mint_msg) => ExecuteMsg::Mint(mint_msg),
+            NftExecuteMsg::Burn { token_id } => ExecuteMsg::Burn { token_id },
+        };
+
         CarbonableMetadataContract::default().execute(deps, env, info, msg)
     }
 
@@ -50,6 +88,36 @@ pub mod entry {
     }
 }
 
+/// Replace the metadata of a minted token
+///
+/// Only the minter (the sell contract) can update metadata, e.g. to reveal tokens.
+pub fn update_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    extension: Extension,
+) -> Result<Response, ContractError> {
+    let contract = CarbonableMetadataContract::default();
+
+    if contract.minter.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    contract
+        .tokens
+        .update(deps.storage, &token_id, |token| match token {
+            Some(mut token) => {
+                token.extension = extension;
+                Ok(token)
+            }
+            None => Err(ContractError::Std(StdError::not_found("token"))),
+        })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_metadata")
+        .add_attribute("token_id", token_id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +163,57 @@ mod tests {
         assert_eq!(res.token_uri, mint_msg.token_uri);
         assert_eq!(res.extension, mint_msg.extension);
     }
+
+    #[test]
+    fn update_metadata_minter_only() {
+        let mut deps = mock_dependencies();
+        let contract = CarbonableMetadataContract::default();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+        };
+        contract
+            .instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)
+            .unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = MintMsg {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                name: Some("Hidden".to_string()),
+                ..Metadata::default()
+            }),
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::Mint(mint_msg),
+            )
+            .unwrap();
+
+        let revealed = Some(Metadata {
+            name: Some("Starship USS Enterprise".to_string()),
+            ..Metadata::default()
+        });
+
+        let res = update_metadata(
+            deps.as_mut(),
+            mock_info("john", &[]),
+            token_id.to_string(),
+            revealed.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        update_metadata(deps.as_mut(), info, token_id.to_string(), revealed.clone()).unwrap();
+
+        let res = contract.nft_info(deps.as_ref(), token_id.into()).unwrap();
+        assert_eq!(res.extension, revealed);
+    }
 }
@@ -1,271 +1,888 @@
-use crate::msg::{ExecuteMsg, WhiteListEntry};
+use crate::msg::{
+    CollectionConfig, ExecuteMsg, OracleConfig, OraclePriceResponse, OracleQueryMsg, Permit,
+    PermitPayload, RevealEntry, SealedBidPayload, WhiteListEntry,
+};
 use crate::state::{
-    State, ADMIN_WALLETS, NFT_CONTRACT, OWNER_WALLET, PRE_SELL_MODE, SELL_MODE, STATE, WHITELIST,
+    Auction, AuctionConfig, Bid, Collection, ConfigChange, CurrentRound, Discount, FiatOrder,
+    GeneratedAttribute, InventoryOrder, Lottery, LotteryConfig, LotteryEntry, LoyaltyConfig,
+    LoyaltyTier, MetadataTemplate, MintPhase, Oracle, PendingChange, Plot, PlotEntry, Product,
+    Provenance, RoundConfig, RoundStats, SealedAuction, SealedAuctionConfig, SealedBid,
+    SealedWinner, State, SupplyKind, TokenID, Voucher, WaitlistEntry, WithdrawAmount,
+    WithdrawConfig, WithdrawProposal, ADMIN_WALLETS, AUCTIONS, AUCTION_BIDS, AUCTION_COUNT,
+    AUTO_RELEASE_RESERVED, BUYERS, COLLECTIONS, CURRENT_ROUND, ESCROWED, FIAT_ORDERS,
+    FULFILLER_WALLETS, GATING_CONTRACTS, GENERATED_ATTRIBUTES, INVENTORY, INVENTORY_COUNT,
+    INVENTORY_DEPOSITS, INVENTORY_MODE, LOTTERIES, LOTTERY_COUNT, LOTTERY_ENTRIES, LOYALTY,
+    METADATA_TEMPLATES, NFT_CONTRACT, ORACLE, OWNER_WALLET, PENDING_CHANGES, PENDING_CHANGE_COUNT,
+    PERMIT_SIGNER, PLOTS, PRE_SELL_MODE, PRODUCTS, PROVENANCE, REFERRALS, REFERRAL_SHARE, ROUNDS,
+    ROUND_PURCHASES, ROUND_STATS, ROUND_WHITELIST, SALE_STATS, SEALED_AUCTIONS, SEALED_BIDS,
+    SELL_MODE, STATE, TIMELOCK_DELAY, TREASURY_WALLETS, USED_GATING_TOKENS, USED_LOYALTY_TOKENS,
+    USED_PERMIT_NONCES, VOUCHERS, WAITLIST, WAITLIST_COUNT, WHITELIST, WITHDRAW_CONFIG,
+    WITHDRAW_PROPOSALS, WITHDRAW_PROPOSAL_COUNT,
 };
 use crate::ContractError;
 use cosmwasm_std::{
-    has_coins, to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response,
-    Uint128, WasmMsg,
+    has_coins, to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw_carbonable_lib::{Extension, Metadata};
+use cw721::{Cw721QueryMsg, Cw721ReceiveMsg, TokensResponse};
+use cw_carbonable_lib::{Extension, Metadata, NftExecuteMsg, Trait};
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 
 pub fn _execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Buy {} => try_buy(deps, info),
-        ExecuteMsg::MultiBuy { quantity } => try_multi_buy(deps, info, quantity),
-        ExecuteMsg::Airdrop { receivers } => try_airdrop(deps, info, receivers),
-        ExecuteMsg::Withdraw { wallet, coin } => try_withdraw(deps, info, wallet, coin),
+        ExecuteMsg::Buy {
+            permit,
+            referrer,
+            voucher,
+            recipient,
+            product_id,
+            collection_id,
+            max_price,
+        } => try_buy(
+            deps,
+            env,
+            info,
+            BuyOptions {
+                permit,
+                referrer,
+                voucher,
+                recipients: recipient.map(|recipient| vec![recipient]),
+                product_id,
+                collection_id,
+                max_price,
+            },
+        ),
+        ExecuteMsg::MultiBuy {
+            quantity,
+            permit,
+            referrer,
+            voucher,
+            recipients,
+            product_id,
+            collection_id,
+            max_price,
+        } => try_multi_buy(
+            deps,
+            env,
+            info,
+            quantity,
+            BuyOptions {
+                permit,
+                referrer,
+                voucher,
+                recipients,
+                product_id,
+                collection_id,
+                max_price,
+            },
+        ),
+        ExecuteMsg::UpdateReferralShare { share } => update_referral_share(deps, info, share),
+        ExecuteMsg::ClaimReferralRewards {} => try_claim_referral_rewards(deps, info),
+        ExecuteMsg::CreateVoucher {
+            code_hash,
+            discount,
+            max_redemptions,
+            expires,
+            allowed_address,
+        } => create_voucher(
+            deps,
+            info,
+            code_hash,
+            discount,
+            max_redemptions,
+            expires,
+            allowed_address,
+        ),
+        ExecuteMsg::RemoveVoucher { code_hash } => remove_voucher(deps, info, code_hash),
+        ExecuteMsg::Airdrop { receivers } => try_airdrop(deps, env, info, receivers),
+        ExecuteMsg::Withdraw { wallet, coin } => try_withdraw(deps, env, info, wallet, coin),
+        ExecuteMsg::WithdrawAll { wallet, denom } => {
+            try_withdraw_all(deps, env, info, wallet, denom)
+        }
+        ExecuteMsg::AddTreasuryWallet { address } => add_treasury_wallet(deps, env, info, address),
+        ExecuteMsg::RemoveTreasuryWallet { address } => {
+            remove_treasury_wallet(deps, env, info, address)
+        }
+        ExecuteMsg::ApproveWithdraw { proposal_id } => {
+            try_approve_withdraw(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::CancelWithdraw { proposal_id } => try_cancel_withdraw(deps, info, proposal_id),
+        ExecuteMsg::UpdateWithdrawConfig {
+            threshold,
+            proposal_duration,
+        } => update_withdraw_config(deps, info, threshold, proposal_duration),
         ExecuteMsg::PreSellMode { enable } => try_pre_sell_mode(deps, info, enable),
-        ExecuteMsg::SellMode { enable } => try_sell_mode(deps, info, enable),
+        ExecuteMsg::SellMode { enable } => try_sell_mode(deps, env, info, enable),
+        ExecuteMsg::UpdateGatingContracts { contracts } => {
+            update_gating_contracts(deps, info, contracts)
+        }
+        ExecuteMsg::UpdateLoyalty { contracts, tiers } => {
+            update_loyalty(deps, info, contracts, tiers)
+        }
+        ExecuteMsg::UpdateOracle { oracle } => update_oracle(deps, env, info, oracle),
         ExecuteMsg::AddToWhitelist { entries } => try_update_whitelist(deps, info, entries),
-        ExecuteMsg::UpdatePrice { price } => update_price(deps, info, price),
+        ExecuteMsg::SetPermitSigner { pubkey } => set_permit_signer(deps, env, info, pubkey),
+        ExecuteMsg::SetCollection {
+            collection_id,
+            config,
+        } => set_collection(deps, env, info, collection_id, config),
+        ExecuteMsg::SetPlots { plots } => set_plots(deps, env, info, plots),
+        ExecuteMsg::RemovePlot { token_id } => remove_plot(deps, info, token_id),
+        ExecuteMsg::JoinWaitlist {
+            quantity,
+            max_price,
+        } => try_join_waitlist(deps, env, info, quantity, max_price),
+        ExecuteMsg::LeaveWaitlist { entry_id } => try_leave_waitlist(deps, info, entry_id),
+        ExecuteMsg::FulfillWaitlist { limit } => try_fulfill_waitlist(deps, env, limit),
+        ExecuteMsg::BuySpecific {
+            token_ids,
+            max_price,
+        } => try_buy_specific(deps, env, info, token_ids, max_price),
+        ExecuteMsg::SetProduct {
+            product_id,
+            name,
+            price,
+            supply,
+            metadata,
+        } => set_product(
+            deps,
+            env,
+            info,
+            product_id,
+            Product {
+                name,
+                price,
+                supply,
+                minted: 0,
+                metadata,
+            },
+        ),
+        ExecuteMsg::RemoveProduct { product_id } => remove_product(deps, info, product_id),
+        ExecuteMsg::SetRound { round_id, config } => set_round(deps, env, info, round_id, config),
+        ExecuteMsg::RemoveRound { round_id } => remove_round(deps, info, round_id),
+        ExecuteMsg::AddToRoundWhitelist {
+            round_id,
+            addresses,
+        } => add_to_round_whitelist(deps, info, round_id, addresses),
+        ExecuteMsg::ActivateRound { round_id } => activate_round(deps, info, round_id),
+        ExecuteMsg::CloseRound {} => close_round(deps, info),
+        ExecuteMsg::CreateAuction { config } => create_auction(deps, info, config),
+        ExecuteMsg::CancelAuction { auction_id } => cancel_auction(deps, info, auction_id),
+        ExecuteMsg::PlaceBid { auction_id } => try_place_bid(deps, env, info, auction_id),
+        ExecuteMsg::SettleAuction { auction_id } => try_settle_auction(deps, env, auction_id),
+        ExecuteMsg::CreateSealedAuction { config } => create_sealed_auction(deps, info, config),
+        ExecuteMsg::CommitSealedBid {
+            auction_id,
+            commitment,
+        } => try_commit_sealed_bid(deps, env, info, auction_id, commitment),
+        ExecuteMsg::RevealSealedBid {
+            auction_id,
+            price,
+            salt,
+        } => try_reveal_sealed_bid(deps, env, info, auction_id, price, salt),
+        ExecuteMsg::SettleSealedAuction { auction_id, limit } => {
+            try_settle_sealed_auction(deps, env, auction_id, limit)
+        }
+        ExecuteMsg::ClaimSealedRefund { auction_id } => {
+            try_claim_sealed_refund(deps, env, info, auction_id)
+        }
+        ExecuteMsg::CreateLottery { config } => create_lottery(deps, env, info, config),
+        ExecuteMsg::RegisterLottery { quantity } => try_register_lottery(deps, env, info, quantity),
+        ExecuteMsg::DrawLottery { seed } => try_draw_lottery(deps, env, info, seed),
+        ExecuteMsg::ClaimLottery { lottery_id } => try_claim_lottery(deps, env, info, lottery_id),
+        ExecuteMsg::ReceiveNft(msg) => try_receive_nft(deps, info, msg),
+        ExecuteMsg::UpdateInventoryMode { mode } => update_inventory_mode(deps, info, mode),
+        ExecuteMsg::WithdrawInventory {
+            token_ids,
+            recipient,
+        } => try_withdraw_inventory(deps, info, token_ids, recipient),
+        ExecuteMsg::UpdatePrice { price } => update_price(deps, env, info, price),
         ExecuteMsg::UpdateSupply {
             reserved_supply,
             market_supply,
-        } => update_supply(deps, info, reserved_supply, market_supply),
+        } => update_supply(deps, env, info, reserved_supply, market_supply),
+        ExecuteMsg::MoveSupply { from, to, amount } => {
+            try_move_supply(deps, env, info, from, to, amount)
+        }
+        ExecuteMsg::UpdateAutoReleaseReserved { enable } => {
+            update_auto_release_reserved(deps, info, enable)
+        }
         ExecuteMsg::UpdateMetadata { metadata } => update_metadata(deps, info, metadata),
-        ExecuteMsg::UpdateNftContract { address } => update_nft_contract(deps, info, address),
+        ExecuteMsg::UpdateGeneratedAttributes { attributes } => {
+            update_generated_attributes(deps, info, attributes)
+        }
+        ExecuteMsg::SetMetadataTemplate {
+            template_id,
+            metadata,
+            weight,
+            supply,
+        } => set_metadata_template(deps, info, template_id, metadata, weight, supply),
+        ExecuteMsg::RemoveMetadataTemplate { template_id } => {
+            remove_metadata_template(deps, info, template_id)
+        }
+        ExecuteMsg::CommitProvenance {
+            provenance_hash,
+            seed_hash,
+        } => commit_provenance(deps, info, provenance_hash, seed_hash),
+        ExecuteMsg::Reveal { item_hashes, seed } => try_reveal(deps, info, item_hashes, seed),
+        ExecuteMsg::RevealBatch { entries } => try_reveal_batch(deps, info, entries),
+        ExecuteMsg::UpdateNftContract { address } => update_nft_contract(deps, env, info, address),
+        ExecuteMsg::UpdateTimelockDelay { delay } => update_timelock_delay(deps, env, info, delay),
+        ExecuteMsg::ExecutePendingChange { change_id } => {
+            try_execute_pending_change(deps, env, info, change_id)
+        }
+        ExecuteMsg::CancelPendingChange { change_id } => {
+            try_cancel_pending_change(deps, info, change_id)
+        }
         ExecuteMsg::RemoveAdmin { address } => remove_admin(deps, info, address),
         ExecuteMsg::AddAdmin { address } => add_admin(deps, info, address),
+        ExecuteMsg::RemoveFulfiller { address } => remove_fulfiller(deps, info, address),
+        ExecuteMsg::AddFulfiller { address } => add_fulfiller(deps, info, address),
+        ExecuteMsg::FulfillFiatOrder {
+            order_id,
+            recipient,
+            quantity,
+        } => try_fulfill_fiat_order(deps, env, info, order_id, recipient, quantity),
     }
 }
 
-pub fn try_buy(mut deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    // load state
-    let mut state = STATE.load(deps.storage)?;
-
-    is_sell_available(&mut deps, &info, 1)?;
-
-    state.total_market_minted += 1;
-    // Is some NFT available ?
-    is_market_nft_available(&state)?;
+/// Optional parameters of Buy and MultiBuy
+#[derive(Default)]
+pub struct BuyOptions {
+    pub permit: Option<Permit>,
+    pub referrer: Option<String>,
+    pub voucher: Option<String>,
+    pub recipients: Option<Vec<String>>,
+    pub product_id: Option<u32>,
+    pub collection_id: Option<u32>,
+    /// highest total price accepted, against oracle price moves
+    pub max_price: Option<Coin>,
+}
 
-    // Does the buy has enough coins ?
-    if !has_coins(info.funds.as_slice(), &state.sell_price) {
-        return Err(ContractError::NotEnoughMoneyForNft {});
-    }
+pub fn try_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    options: BuyOptions,
+) -> Result<Response, ContractError> {
+    let response = buy_helper(deps, env, &info, 1, options)?;
 
-    // Bump last_token_id ?
-    state.last_token_id += 1;
+    Ok(response.add_attribute("method", "try_buy"))
+}
 
-    let response = Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
-        msg: to_binary(&mint_helper(
-            state.last_token_id,
-            info.sender.to_string(),
-            state.clone(),
-        ))?,
-        funds: vec![],
-    }));
+pub fn try_multi_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    quantity: u32,
+    options: BuyOptions,
+) -> Result<Response, ContractError> {
+    // Check quantity validity
+    is_multi_buy_quantity_too_big(quantity, &STATE.load(deps.storage)?)?;
 
-    // Update contract state
-    STATE.save(deps.storage, &state)?;
+    let response = buy_helper(deps, env, &info, quantity, options)?;
 
-    Ok(response.add_attribute("method", "try_buy"))
+    Ok(response.add_attribute("method", "try_multi_buy"))
 }
 
-pub fn try_multi_buy(
+/// Buy `quantity` tokens on the market supply and mint them to the buyer
+fn buy_helper(
     mut deps: DepsMut,
-    info: MessageInfo,
+    env: Env,
+    info: &MessageInfo,
     quantity: u32,
+    options: BuyOptions,
 ) -> Result<Response, ContractError> {
     // load state
     let mut state = STATE.load(deps.storage)?;
 
-    is_sell_available(&mut deps, &info, quantity)?;
+    // Tokens are minted to the recipients, the payer by default
+    let recipients = match options.recipients {
+        Some(recipients) => {
+            if recipients.len() != quantity as usize {
+                return Err(ContractError::InvalidRecipients {});
+            }
+            recipients
+                .iter()
+                .map(|recipient| deps.api.addr_validate(recipient))
+                .collect::<StdResult<Vec<Addr>>>()?
+        }
+        None => vec![info.sender.clone(); quantity as usize],
+    };
+
+    // A permit replaces the whitelist and the sell price, an open round replaces the sell modes too
+    let round_id = match CURRENT_ROUND.load(deps.storage)? {
+        Some(CurrentRound {
+            round_id,
+            open: true,
+        }) if options.permit.is_none() => Some(round_id),
+        _ => None,
+    };
+    // Collections are sold outside of the rounds
+    if let (Some(round_id), Some(_)) = (round_id, options.collection_id) {
+        return Err(ContractError::RoundOpen { round_id });
+    }
+    let has_permit = options.permit.is_some();
+    let (phase, mut unit_price) = match (options.permit, round_id) {
+        (Some(permit), _) => {
+            let phase = sell_phase(&deps)?;
+            let price = use_permit(
+                &mut deps,
+                &env,
+                info,
+                permit,
+                quantity,
+                options.product_id,
+                options.collection_id,
+            )?;
+            (phase, price)
+        }
+        (None, Some(round_id)) => use_round(&mut deps, &env, info, round_id, quantity)?,
+        (None, None) => (
+            is_sell_available(&mut deps, info, quantity)?,
+            state.sell_price.clone(),
+        ),
+    };
+
+    // A product replaces the sell price and the metadata
+    let product = match options.product_id {
+        Some(product_id) => {
+            let mut product = match PRODUCTS.may_load(deps.storage, product_id)? {
+                Some(product) => product,
+                None => return Err(ContractError::ProductNotFound { product_id }),
+            };
+            product.minted += quantity;
+            is_product_available(&product)?;
+            PRODUCTS.save(deps.storage, product_id, &product)?;
+
+            if !has_permit {
+                unit_price = product.price.clone();
+            }
+            Some(product)
+        }
+        None => None,
+    };
+
+    // A collection has its own NFT contract, price, supply, metadata and token ids
+    let mut collection = match options.collection_id {
+        Some(collection_id) => {
+            if options.product_id.is_some() {
+                return Err(ContractError::ProductNotInCollection {});
+            }
+            let mut collection = match COLLECTIONS.may_load(deps.storage, collection_id)? {
+                Some(collection) => collection,
+                None => return Err(ContractError::CollectionNotFound { collection_id }),
+            };
+            collection.minted += quantity;
+            is_collection_available(&collection)?;
+
+            if !has_permit {
+                unit_price = collection.price.clone();
+            }
+            Some(collection)
+        }
+        None => None,
+    };
+
+    // Holders of the loyalty collections pay a discounted sell price
+    if !has_permit && round_id.is_none() && product.is_none() && collection.is_none() {
+        let (tokens, discount) = loyalty_discount(deps.as_ref(), &info.sender)?;
+        unit_price.amount -= unit_price.amount * discount;
+
+        // The tokens giving the discount can't be used by another buyer
+        if !discount.is_zero() {
+            for key in tokens {
+                USED_LOYALTY_TOKENS.save(deps.storage, key, &info.sender)?;
+            }
+        }
+    }
+
+    // Prices in the oracle reference currency are paid in the payment denom
+    unit_price = convert_price(deps.as_ref(), &env, unit_price)?;
 
-    // Check quantity validity
-    is_multi_buy_quantity_too_big(quantity, &state)?;
+    // A voucher lowers the unit price, fixed amounts are in the payment denom
+    if let Some(code) = options.voucher {
+        unit_price = redeem_voucher(&mut deps, &env, info, &code, unit_price)?;
+    }
 
-    state.total_market_minted += quantity;
-    // Is some NFT available ?
-    is_market_nft_available(&state)?;
+    // Inventory mode, the main sale transfers pre-minted tokens instead of minting them
+    let inventory = match (&collection, &product) {
+        (None, None) => INVENTORY_MODE.load(deps.storage)?,
+        _ => None,
+    };
+
+    if collection.is_none() && inventory.is_none() {
+        is_waitlist_served(&deps, &state)?;
+        state.total_market_minted += quantity;
+        // Is some NFT available ?
+        is_market_nft_available(&state)?;
+    }
 
     // Does the buy has enough coins ?
-    let mut sell_price = state.sell_price.clone();
+    let mut sell_price = unit_price;
     sell_price.amount *= Uint128::from(quantity);
-    if !has_coins(info.funds.as_slice(), &sell_price) {
-        return Err(ContractError::NotEnoughMoneyForNft {});
+    is_price_paid(info, &sell_price, &options.max_price)?;
+    record_sale(
+        &mut deps,
+        &env,
+        &info.sender,
+        phase,
+        quantity,
+        std::slice::from_ref(&sell_price),
+    )?;
+    if let Some(round_id) = round_id {
+        ROUND_STATS.update(deps.storage, round_id, |stats| -> StdResult<_> {
+            let mut stats = stats.unwrap_or_default();
+            add_coin(&mut stats.revenue, &sell_price);
+            Ok(stats)
+        })?;
     }
 
-    let mut response = Response::new();
-    for _ in 0..quantity {
-        // Bump last_token_id ?
-        state.last_token_id += 1;
+    let mut response = Response::new().add_attribute("payer", info.sender.to_string());
+    if let Some(product_id) = options.product_id {
+        response = response.add_attribute("product_id", product_id.to_string());
+    }
+    if let Some(collection_id) = options.collection_id {
+        response = response.add_attribute("collection_id", collection_id.to_string());
+    }
+
+    // Credit the referrer
+    if let Some(referrer) = options.referrer {
+        let referrer = credit_referrer(&mut deps, info, referrer, quantity, &sell_price)?;
+        response = response.add_attribute("referrer", referrer);
+    }
+
+    for (draw, recipient) in recipients.into_iter().enumerate() {
+        if let Some(order) = &inventory {
+            let token_id = take_from_inventory(&mut deps, &env, &info.sender, order, draw as u32)?;
+            response = response
+                .add_message(transfer_helper(
+                    &NFT_CONTRACT.load(deps.storage)?,
+                    token_id.clone(),
+                    &recipient,
+                )?)
+                .add_attribute("recipient", recipient.to_string())
+                .add_attribute("token_id", token_id);
+            continue;
+        }
+
+        let (nft_id, nft_contract, base, edition_size) = match collection.as_mut() {
+            Some(collection) => {
+                collection.last_token_id += 1;
+                (
+                    collection.last_token_id,
+                    collection.nft_contract.clone(),
+                    collection.metadata.clone(),
+                    collection.supply,
+                )
+            }
+            None => {
+                // Bump last_token_id ?
+                next_token_id(&deps, &mut state);
+
+                let base = match &product {
+                    Some(product) => product.metadata.clone(),
+                    None => {
+                        pick_metadata_template(&mut deps, &env, &info.sender, state.last_token_id)?
+                            .unwrap_or_else(|| state.metadata.clone())
+                    }
+                };
+                (
+                    state.last_token_id,
+                    NFT_CONTRACT.load(deps.storage)?,
+                    base,
+                    state.total_market_supply + state.total_reserved_supply,
+                )
+            }
+        };
 
+        let metadata = token_metadata(&deps, &env, edition_size, base, nft_id, phase)?;
         response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
-            msg: to_binary(&mint_helper(
-                state.last_token_id,
-                info.sender.to_string(),
-                state.clone(),
-            ))?,
+            contract_addr: nft_contract.to_string(),
+            msg: to_json_binary(&mint_helper(nft_id, recipient.to_string(), metadata))?,
             funds: vec![],
         }));
+        response = response.add_attribute("recipient", recipient.to_string());
     }
 
     // Update contract state
     STATE.save(deps.storage, &state)?;
+    if let (Some(collection_id), Some(mut collection)) = (options.collection_id, collection) {
+        add_coin(&mut collection.revenue, &sell_price);
+        COLLECTIONS.save(deps.storage, collection_id, &collection)?;
+    }
 
-    Ok(response.add_attribute("method", "try_multi_buy"))
+    Ok(response)
 }
 
-pub fn update_nft_contract(
+pub fn update_referral_share(
     deps: DepsMut,
     info: MessageInfo,
-    address: String,
+    share: Decimal,
 ) -> Result<Response, ContractError> {
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    // check validity of new admin address
-    let contract_address = deps.api.addr_validate(address.as_str());
-    if contract_address.is_err() {
-        return Err(ContractError::InvalidAddress { address });
+    if share > Decimal::one() {
+        return Err(ContractError::InvalidReferralShare {});
     }
 
-    NFT_CONTRACT.save(deps.storage, &contract_address.unwrap())?;
+    REFERRAL_SHARE.save(deps.storage, &share)?;
 
-    Ok(Response::new().add_attribute("method", "update_nft_contract"))
+    Ok(Response::new().add_attribute("method", "update_referral_share"))
 }
 
-pub fn update_metadata(
+pub fn try_claim_referral_rewards(
     deps: DepsMut,
     info: MessageInfo,
-    metadata: Metadata,
 ) -> Result<Response, ContractError> {
-    // load state
-    let mut state = STATE.load(deps.storage)?;
+    let mut stats = REFERRALS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    if stats.claimable.is_empty() {
+        return Err(ContractError::NoReferralRewards {});
+    }
+
+    for c in stats.claimable.iter() {
+        release(deps.storage, c)?;
+    }
+    let send_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: stats.claimable,
+    });
+
+    stats.claimable = vec![];
+    REFERRALS.save(deps.storage, info.sender, &stats)?;
+
+    Ok(Response::new()
+        .add_message(send_msg)
+        .add_attribute("method", "try_claim_referral_rewards"))
+}
+
+/// Credit the referrer of a purchase with its share of the payment
+fn credit_referrer(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    referrer: String,
+    quantity: u32,
+    payment: &Coin,
+) -> Result<Addr, ContractError> {
+    let referrer = match deps.api.addr_validate(&referrer) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address: referrer }),
+    };
+    if referrer == info.sender {
+        return Err(ContractError::SelfReferral {});
+    }
 
+    let reward = Coin {
+        denom: payment.denom.clone(),
+        amount: payment.amount * REFERRAL_SHARE.load(deps.storage)?,
+    };
+
+    let mut stats = REFERRALS
+        .may_load(deps.storage, referrer.clone())?
+        .unwrap_or_default();
+    stats.nb_purchases += 1;
+    stats.nb_tokens += quantity;
+    add_coin(&mut stats.total_earned, &reward);
+    add_coin(&mut stats.claimable, &reward);
+    REFERRALS.save(deps.storage, referrer.clone(), &stats)?;
+    escrow(deps.storage, &reward)?;
+
+    Ok(referrer)
+}
+
+pub fn create_voucher(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_hash: String,
+    discount: Discount,
+    max_redemptions: u32,
+    expires: Option<u64>,
+    allowed_address: Option<String>,
+) -> Result<Response, ContractError> {
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    // check validity of new admin address
-    state.metadata = metadata;
+    is_hash_valid(&code_hash)?;
+    let code_hash = code_hash.to_lowercase();
+    if VOUCHERS.has(deps.storage, &code_hash) {
+        return Err(ContractError::VoucherAlreadyExists {});
+    }
 
-    // Update contract state
-    STATE.save(deps.storage, &state)?;
+    if let Discount::Percent(percent) = discount {
+        if percent > Decimal::one() {
+            return Err(ContractError::InvalidDiscount {});
+        }
+    }
 
-    Ok(Response::new().add_attribute("method", "update_metadata"))
+    let allowed_address = match allowed_address {
+        Some(address) => match deps.api.addr_validate(&address) {
+            Ok(addr) => Some(addr),
+            Err(_) => return Err(ContractError::InvalidAddress { address }),
+        },
+        None => None,
+    };
+
+    VOUCHERS.save(
+        deps.storage,
+        &code_hash,
+        &Voucher {
+            discount,
+            max_redemptions,
+            redemptions: 0,
+            expires,
+            allowed_address,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("method", "create_voucher"))
 }
 
-pub fn try_airdrop(
+pub fn remove_voucher(
     deps: DepsMut,
     info: MessageInfo,
-    receiver: Vec<String>,
+    code_hash: String,
 ) -> Result<Response, ContractError> {
-    // load state
-    let mut state = STATE.load(deps.storage)?;
-
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    state.total_reserved_minted += receiver.len() as u32;
-    // Is some NFT available ?
-    is_reserved_nft_available(&state)?;
+    let code_hash = code_hash.to_lowercase();
+    if !VOUCHERS.has(deps.storage, &code_hash) {
+        return Err(ContractError::VoucherNotFound {});
+    }
 
-    // Check validity of drop receivers addresses
-    is_addresses_valid(&deps, &receiver)?;
+    VOUCHERS.remove(deps.storage, &code_hash);
 
-    // Mint
-    let mut response = Response::new();
-    for recv in receiver {
-        state.last_token_id += 1;
+    Ok(Response::new().add_attribute("method", "remove_voucher"))
+}
 
-        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
-            msg: to_binary(&mint_helper(
-                state.last_token_id,
-                recv.to_string(),
-                state.clone(),
-            ))?,
-            funds: vec![],
-        }))
+/// Redeem a voucher code and return the discounted unit price
+fn redeem_voucher(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    code: &str,
+    unit_price: Coin,
+) -> Result<Coin, ContractError> {
+    let code_hash = hex::encode(Sha256::digest(code.as_bytes()));
+    let mut voucher = match VOUCHERS.may_load(deps.storage, &code_hash)? {
+        Some(voucher) => voucher,
+        None => return Err(ContractError::VoucherNotFound {}),
+    };
+
+    if let Some(expires) = voucher.expires {
+        if env.block.time.seconds() > expires {
+            return Err(ContractError::VoucherExpired {});
+        }
     }
 
-    // Update contract state
-    STATE.save(deps.storage, &state)?;
+    if voucher.redemptions >= voucher.max_redemptions {
+        return Err(ContractError::VoucherExhausted {});
+    }
 
-    Ok(response.add_attribute("method", "try_airdrop"))
+    if let Some(allowed_address) = &voucher.allowed_address {
+        if *allowed_address != info.sender {
+            return Err(ContractError::VoucherNotAllowed {});
+        }
+    }
+
+    voucher.redemptions += 1;
+    VOUCHERS.save(deps.storage, &code_hash, &voucher)?;
+
+    let amount = match voucher.discount {
+        Discount::Percent(percent) => unit_price.amount - unit_price.amount * percent,
+        Discount::Fixed(amount) => unit_price.amount.saturating_sub(amount),
+    };
+
+    Ok(Coin {
+        denom: unit_price.denom,
+        amount,
+    })
 }
 
-pub fn try_withdraw(
+/// Add a coin to a list of coins, merging denoms
+pub fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) {
+    if coin.amount.is_zero() {
+        return;
+    }
+
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(c) => c.amount += coin.amount,
+        None => coins.push(coin.clone()),
+    }
+}
+
+pub fn commit_provenance(
     deps: DepsMut,
     info: MessageInfo,
-    wallet: Addr,
-    coin: Vec<Coin>,
+    provenance_hash: String,
+    seed_hash: String,
 ) -> Result<Response, ContractError> {
-    // Is admin or owner wallet ?
-    is_admin_or_owner(&deps, info)?;
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    is_hash_valid(&provenance_hash)?;
+    is_hash_valid(&seed_hash)?;
+
+    // once a token is minted, the commitment can't change anymore
+    let state = STATE.load(deps.storage)?;
+    if state.total_market_minted + state.total_reserved_minted > 0 {
+        return Err(ContractError::ProvenanceLocked {});
+    }
+
+    PROVENANCE.save(
+        deps.storage,
+        &Provenance {
+            provenance_hash: provenance_hash.to_lowercase(),
+            seed_hash: seed_hash.to_lowercase(),
+            item_hashes: vec![],
+            seed: None,
+            offset: None,
+        },
+    )?;
 
-    let send_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: wallet.to_string(),
-        amount: coin,
-    });
     Ok(Response::new()
-        .add_message(send_msg)
-        .add_attribute("method", "try_withdraw"))
+        .add_attribute("method", "commit_provenance")
+        .add_attribute("provenance_hash", provenance_hash))
 }
 
-pub fn try_sell_mode(
+pub fn try_reveal(
     deps: DepsMut,
     info: MessageInfo,
-    enable: bool,
+    item_hashes: Vec<String>,
+    seed: String,
 ) -> Result<Response, ContractError> {
-    // Is admin or owner wallet ?
-    is_admin_or_owner(&deps, info)?;
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    let mut provenance = match PROVENANCE.may_load(deps.storage)? {
+        Some(provenance) => provenance,
+        None => return Err(ContractError::ProvenanceNotCommitted {}),
+    };
+    if provenance.offset.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
 
-    SELL_MODE.save(deps.storage, &enable)?;
+    // Is sale closed ?
+    if SELL_MODE.load(deps.storage)? || PRE_SELL_MODE.load(deps.storage)? {
+        return Err(ContractError::RevealDuringSale {});
+    }
+
+    // Published hashes must match the commitment
+    let item_hashes: Vec<String> = item_hashes.iter().map(|h| h.to_lowercase()).collect();
+    if item_hashes.is_empty()
+        || hex::encode(Sha256::digest(item_hashes.concat().as_bytes()))
+            != provenance.provenance_hash
+    {
+        return Err(ContractError::ProvenanceHashMismatch {});
+    }
+
+    // Every token gets a final metadata
+    let state = STATE.load(deps.storage)?;
+    if item_hashes.len() < (state.total_market_supply + state.total_reserved_supply) as usize {
+        return Err(ContractError::ProvenanceIncomplete {});
+    }
+
+    // Draw the offset from the committed seed, the owner can't choose it at the reveal
+    if hex::encode(Sha256::digest(seed.as_bytes())) != provenance.seed_hash {
+        return Err(ContractError::ProvenanceSeedMismatch {});
+    }
+    let offset = (lottery_random(&seed, state.last_token_id) % item_hashes.len() as u64) as u32;
+
+    provenance.item_hashes = item_hashes;
+    provenance.seed = Some(seed);
+    provenance.offset = Some(offset);
+    PROVENANCE.save(deps.storage, &provenance)?;
 
-    Ok(Response::new().add_attribute("method", "try_sell_mode"))
+    Ok(Response::new()
+        .add_attribute("method", "try_reveal")
+        .add_attribute("offset", offset.to_string()))
 }
 
-pub fn try_pre_sell_mode(
+pub fn try_reveal_batch(
     deps: DepsMut,
     info: MessageInfo,
-    enable: bool,
+    entries: Vec<RevealEntry>,
 ) -> Result<Response, ContractError> {
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    PRE_SELL_MODE.save(deps.storage, &enable)?;
+    let provenance = PROVENANCE.may_load(deps.storage)?;
+    let (item_hashes, offset) = match provenance {
+        Some(Provenance {
+            item_hashes,
+            offset: Some(offset),
+            ..
+        }) => (item_hashes, offset),
+        _ => return Err(ContractError::NotRevealed {}),
+    };
 
-    Ok(Response::new().add_attribute("method", "try_pre_sell_mode"))
+    let state = STATE.load(deps.storage)?;
+    let nft_contract = NFT_CONTRACT.load(deps.storage)?;
+
+    let mut response = Response::new();
+    for entry in entries {
+        if entry.token_id == 0 || entry.token_id > state.last_token_id {
+            return Err(ContractError::InvalidRevealMetadata {
+                token_id: entry.token_id,
+            });
+        }
+
+        // Check metadata against the committed hashes
+        let index = ((entry.token_id - 1) as u64 + offset as u64) % item_hashes.len() as u64;
+        if hex::encode(Sha256::digest(to_json_vec(&entry.metadata)?)) != item_hashes[index as usize]
+        {
+            return Err(ContractError::InvalidRevealMetadata {
+                token_id: entry.token_id,
+            });
+        }
+
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: nft_contract.to_string(),
+            msg: to_json_binary(&NftExecuteMsg::UpdateMetadata {
+                token_id: entry.token_id.to_string(),
+                extension: Some(entry.metadata),
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(response.add_attribute("method", "try_reveal_batch"))
 }
 
-pub fn try_update_whitelist(
+pub fn update_nft_contract(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    entries: Vec<WhiteListEntry>,
+    address: String,
 ) -> Result<Response, ContractError> {
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    // Update contract state
-    for authorized in entries {
-        match deps.api.addr_validate(&authorized.address) {
-            Ok(addr) => WHITELIST.save(deps.storage, addr, &authorized.nb_slots)?,
-            Err(_e) => {
-                return Err(ContractError::InvalidAddress {
-                    address: authorized.address,
-                })
-            }
-        }
+    // check validity of new admin address
+    let contract_address = deps.api.addr_validate(address.as_str());
+    if contract_address.is_err() {
+        return Err(ContractError::InvalidAddress { address });
     }
 
-    Ok(Response::new().add_attribute("method", "update_price"))
+    let response = schedule_change(deps, &env, ConfigChange::NftContract(contract_address?))?;
+
+    Ok(response.add_attribute("method", "update_nft_contract"))
 }
 
-pub fn update_price(
+pub fn update_metadata(
     deps: DepsMut,
     info: MessageInfo,
-    price: Coin,
+    metadata: Metadata,
 ) -> Result<Response, ContractError> {
     // load state
     let mut state = STATE.load(deps.storage)?;
@@ -273,159 +890,3090 @@ pub fn update_price(
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    state.sell_price = price;
+    // check validity of new admin address
+    state.metadata = metadata;
 
     // Update contract state
     STATE.save(deps.storage, &state)?;
 
-    Ok(Response::new().add_attribute("method", "update_price"))
+    Ok(Response::new().add_attribute("method", "update_metadata"))
 }
 
-pub fn update_supply(
+pub fn update_generated_attributes(
     deps: DepsMut,
     info: MessageInfo,
-    reserved_supply: u32,
-    market_supply: u32,
+    attributes: Vec<GeneratedAttribute>,
 ) -> Result<Response, ContractError> {
-    // load state
-    let mut state = STATE.load(deps.storage)?;
-
     // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    state.total_market_supply = market_supply;
-    state.total_reserved_supply = reserved_supply;
-
-    // Check if new supply is ok
-    is_market_nft_available(&state)?;
-    is_reserved_nft_available(&state)?;
-
-    // Update contract state
-    STATE.save(deps.storage, &state)?;
+    GENERATED_ATTRIBUTES.save(deps.storage, &attributes)?;
 
-    Ok(Response::new().add_attribute("method", "update_price"))
+    Ok(Response::new().add_attribute("method", "update_generated_attributes"))
 }
 
-pub fn remove_admin(
+pub fn set_metadata_template(
     deps: DepsMut,
     info: MessageInfo,
-    address: String,
+    template_id: u32,
+    metadata: Metadata,
+    weight: u32,
+    supply: u32,
 ) -> Result<Response, ContractError> {
-    // Security : owner has all rights
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // a template without weight can never be picked
+    if weight == 0 {
+        return Err(ContractError::InvalidTemplateWeight {});
+    }
+
+    METADATA_TEMPLATES.save(
+        deps.storage,
+        template_id,
+        &MetadataTemplate {
+            metadata,
+            weight,
+            remaining: supply,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("method", "set_metadata_template"))
+}
+
+pub fn remove_metadata_template(
+    deps: DepsMut,
+    info: MessageInfo,
+    template_id: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if !METADATA_TEMPLATES.has(deps.storage, template_id) {
+        return Err(ContractError::MetadataTemplateNotFound { template_id });
+    }
+
+    METADATA_TEMPLATES.remove(deps.storage, template_id);
+
+    Ok(Response::new().add_attribute("method", "remove_metadata_template"))
+}
+
+pub fn try_airdrop(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: Vec<String>,
+) -> Result<Response, ContractError> {
+    // load state
+    let mut state = STATE.load(deps.storage)?;
+
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let nb_receivers = receiver.len() as u32;
+    state.total_reserved_minted += nb_receivers;
+    // Is some NFT available ?
+    is_reserved_nft_available(&state)?;
+
+    // Check validity of drop receivers addresses
+    is_addresses_valid(&deps, &receiver)?;
+
+    // Mint
+    let mut response = Response::new();
+    for recv in receiver {
+        next_token_id(&deps, &mut state);
+
+        let metadata = token_metadata(
+            &deps,
+            &env,
+            state.total_market_supply + state.total_reserved_supply,
+            state.metadata.clone(),
+            state.last_token_id,
+            MintPhase::Airdrop,
+        )?;
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+            msg: to_json_binary(&mint_helper(
+                state.last_token_id,
+                recv.to_string(),
+                metadata,
+            ))?,
+            funds: vec![],
+        }))
+    }
+
+    // Update contract state
+    STATE.save(deps.storage, &state)?;
+
+    SALE_STATS.update(deps.storage, |mut stats| -> StdResult<_> {
+        stats.airdrop_tokens += nb_receivers;
+        Ok(stats)
+    })?;
+
+    Ok(response.add_attribute("method", "try_airdrop"))
+}
+
+fn record_sale(
+    deps: &mut DepsMut,
+    env: &Env,
+    buyer: &Addr,
+    phase: MintPhase,
+    quantity: u32,
+    payments: &[Coin],
+) -> Result<(), ContractError> {
+    let mut stats = SALE_STATS.load(deps.storage)?;
+    for payment in payments {
+        add_coin(&mut stats.revenue, payment);
+    }
+    match phase {
+        MintPhase::PreSale => stats.pre_sale_tokens += quantity,
+        _ => stats.public_sale_tokens += quantity,
+    }
+    if stats.first_sale.is_none() {
+        stats.first_sale = Some(env.block.time.seconds());
+    }
+    stats.last_sale = Some(env.block.time.seconds());
+
+    let bought = BUYERS.may_load(deps.storage, buyer.clone())?;
+    if bought.is_none() {
+        stats.nb_buyers += 1;
+    }
+    BUYERS.save(
+        deps.storage,
+        buyer.clone(),
+        &(bought.unwrap_or(0) + quantity),
+    )?;
+
+    SALE_STATS.save(deps.storage, &stats)?;
+
+    Ok(())
+}
+
+pub fn try_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wallet: String,
+    coin: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let response = propose_withdraw(deps, env, info, wallet, WithdrawAmount::Coins(coin))?;
+
+    Ok(response.add_attribute("method", "try_withdraw"))
+}
+
+pub fn try_withdraw_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wallet: String,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let response = propose_withdraw(deps, env, info, wallet, WithdrawAmount::All(denom))?;
+
+    Ok(response.add_attribute("method", "try_withdraw_all"))
+}
+
+fn propose_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wallet: String,
+    amount: WithdrawAmount,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info.clone())?;
+
+    let wallet = is_treasury_wallet(&deps, wallet)?;
+
+    let config = WITHDRAW_CONFIG.load(deps.storage)?;
+    let proposal_id = WITHDRAW_PROPOSAL_COUNT.load(deps.storage)? + 1;
+    WITHDRAW_PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+
+    // The proposer approves its own proposal
+    let proposal = WithdrawProposal {
+        proposer: info.sender.clone(),
+        wallet,
+        amount,
+        approvers: vec![info.sender],
+        expires: env.block.time.seconds() + config.proposal_duration,
+    };
+
+    let response = Response::new().add_attribute("proposal_id", proposal_id.to_string());
+    approve_withdraw_helper(&mut deps, &env, proposal_id, proposal, response)
+}
+
+pub fn try_approve_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info.clone())?;
+
+    let mut proposal = load_withdraw_proposal(&deps, &env, proposal_id)?;
+    if proposal.approvers.contains(&info.sender) {
+        return Err(ContractError::WithdrawAlreadyApproved { proposal_id });
+    }
+    proposal.approvers.push(info.sender);
+
+    let response = Response::new().add_attribute("method", "try_approve_withdraw");
+    approve_withdraw_helper(&mut deps, &env, proposal_id, proposal, response)
+}
+
+pub fn try_cancel_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let proposal = match WITHDRAW_PROPOSALS.may_load(deps.storage, proposal_id)? {
+        Some(proposal) => proposal,
+        None => return Err(ContractError::WithdrawProposalNotFound { proposal_id }),
+    };
+
+    // Only the proposer or the owner cancel a proposal
+    if proposal.proposer != info.sender {
+        is_owner(&deps, &info)?;
+    }
+
+    WITHDRAW_PROPOSALS.remove(deps.storage, proposal_id);
+
+    Ok(Response::new().add_attribute("method", "try_cancel_withdraw"))
+}
+
+pub fn update_withdraw_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: u32,
+    proposal_duration: u64,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    // The owner and the admins are the approvers
+    let nb_approvers = ADMIN_WALLETS.load(deps.storage)?.len() as u32 + 1;
+    if threshold == 0 || threshold > nb_approvers {
+        return Err(ContractError::InvalidWithdrawThreshold {});
+    }
+
+    WITHDRAW_CONFIG.save(
+        deps.storage,
+        &WithdrawConfig {
+            threshold,
+            proposal_duration,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("method", "update_withdraw_config"))
+}
+
+fn load_withdraw_proposal(
+    deps: &DepsMut,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<WithdrawProposal, ContractError> {
+    let proposal = match WITHDRAW_PROPOSALS.may_load(deps.storage, proposal_id)? {
+        Some(proposal) => proposal,
+        None => return Err(ContractError::WithdrawProposalNotFound { proposal_id }),
+    };
+
+    if env.block.time.seconds() > proposal.expires {
+        return Err(ContractError::WithdrawProposalExpired { proposal_id });
+    }
+
+    Ok(proposal)
+}
+
+/// Save the proposal, or send the funds once enough approvers are still admin or owner
+fn approve_withdraw_helper(
+    deps: &mut DepsMut,
+    env: &Env,
+    proposal_id: u64,
+    proposal: WithdrawProposal,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let owner = OWNER_WALLET.load(deps.storage)?;
+    let admins = ADMIN_WALLETS.load(deps.storage)?;
+    let nb_approvals = proposal
+        .approvers
+        .iter()
+        .filter(|approver| **approver == owner || admins.contains(approver))
+        .count() as u32;
+
+    let response = response.add_attribute("approvals", nb_approvals.to_string());
+    if nb_approvals < WITHDRAW_CONFIG.load(deps.storage)?.threshold {
+        WITHDRAW_PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+        return Ok(response);
+    }
+
+    // Funds owed to referrers stay in the contract
+    let amount = match proposal.amount {
+        WithdrawAmount::Coins(coin) => {
+            for c in coin.iter() {
+                let available = available_funds(deps, env, &c.denom)?;
+                if c.amount > available {
+                    return Err(ContractError::InsufficientFunds {
+                        available: Coin {
+                            denom: c.denom.clone(),
+                            amount: available,
+                        },
+                    });
+                }
+            }
+            coin
+        }
+        WithdrawAmount::All(denom) => {
+            let available = available_funds(deps, env, &denom)?;
+            if available.is_zero() {
+                return Err(ContractError::InsufficientFunds {
+                    available: Coin {
+                        denom,
+                        amount: available,
+                    },
+                });
+            }
+            vec![Coin {
+                denom,
+                amount: available,
+            }]
+        }
+    };
+
+    WITHDRAW_PROPOSALS.remove(deps.storage, proposal_id);
+
+    let send_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: proposal.wallet.to_string(),
+        amount,
+    });
+    Ok(response
+        .add_message(send_msg)
+        .add_attribute("executed", "true"))
+}
+
+/// Add funds to the escrowed total of their denom, they can't be withdrawn
+fn escrow(storage: &mut dyn Storage, funds: &Coin) -> StdResult<()> {
+    ESCROWED.update(storage, &funds.denom, |amount| -> StdResult<_> {
+        Ok(amount.unwrap_or_default() + funds.amount)
+    })?;
+
+    Ok(())
+}
+
+/// Remove funds paid out or earned by the sale from the escrowed total of their denom
+fn release(storage: &mut dyn Storage, funds: &Coin) -> StdResult<()> {
+    ESCROWED.update(storage, &funds.denom, |amount| -> StdResult<_> {
+        Ok(amount.unwrap_or_default().checked_sub(funds.amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Contract balance of a denom, less the escrowed funds: referral rewards, auction bids and
+/// the sealed bid, lottery and waitlist deposits
+fn available_funds(deps: &DepsMut, env: &Env, denom: &str) -> Result<Uint128, ContractError> {
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.clone(), denom)?;
+
+    let locked = ESCROWED.may_load(deps.storage, denom)?.unwrap_or_default();
+
+    Ok(balance.amount.saturating_sub(locked))
+}
+
+fn is_treasury_wallet(deps: &DepsMut, address: String) -> Result<Addr, ContractError> {
+    let wallet = match deps.api.addr_validate(&address) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address }),
+    };
+
+    if !TREASURY_WALLETS.load(deps.storage)?.contains(&wallet) {
+        return Err(ContractError::NotTreasuryWallet { address });
+    }
+
+    Ok(wallet)
+}
+
+pub fn add_treasury_wallet(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    let wallet = match deps.api.addr_validate(&address) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address }),
+    };
+
+    let response = schedule_change(deps, &env, ConfigChange::AddTreasuryWallet(wallet))?;
+
+    Ok(response.add_attribute("method", "add_treasury_wallet"))
+}
+
+pub fn remove_treasury_wallet(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    let wallet = match deps.api.addr_validate(&address) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address }),
+    };
+
+    let response = schedule_change(deps, &env, ConfigChange::RemoveTreasuryWallet(wallet))?;
+
+    Ok(response.add_attribute("method", "remove_treasury_wallet"))
+}
+
+pub fn update_timelock_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    // Shortening the delay waits for the current one
+    let response = schedule_change(deps, &env, ConfigChange::TimelockDelay(delay))?;
+
+    Ok(response.add_attribute("method", "update_timelock_delay"))
+}
+
+pub fn try_execute_pending_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let pending = match PENDING_CHANGES.may_load(deps.storage, change_id)? {
+        Some(pending) => pending,
+        None => return Err(ContractError::PendingChangeNotFound { change_id }),
+    };
+    if env.block.time.seconds() < pending.activation {
+        return Err(ContractError::PendingChangeNotReady {
+            change_id,
+            activation: pending.activation,
+        });
+    }
+
+    PENDING_CHANGES.remove(deps.storage, change_id);
+    let response = apply_change(deps, &env, pending.change)?;
+
+    Ok(response.add_attribute("method", "try_execute_pending_change"))
+}
+
+pub fn try_cancel_pending_change(
+    deps: DepsMut,
+    info: MessageInfo,
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    if !PENDING_CHANGES.has(deps.storage, change_id) {
+        return Err(ContractError::PendingChangeNotFound { change_id });
+    }
+    PENDING_CHANGES.remove(deps.storage, change_id);
+
+    Ok(Response::new().add_attribute("method", "try_cancel_pending_change"))
+}
+
+/// Apply the change now without timelock, queue it otherwise
+fn schedule_change(
+    deps: DepsMut,
+    env: &Env,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    let delay = TIMELOCK_DELAY.load(deps.storage)?;
+    if delay == 0 {
+        return apply_change(deps, env, change);
+    }
+
+    let change_id = PENDING_CHANGE_COUNT.load(deps.storage)? + 1;
+    PENDING_CHANGE_COUNT.save(deps.storage, &change_id)?;
+
+    let activation = env.block.time.seconds() + delay;
+    PENDING_CHANGES.save(
+        deps.storage,
+        change_id,
+        &PendingChange { change, activation },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("change_id", change_id.to_string())
+        .add_attribute("activation", activation.to_string()))
+}
+
+fn apply_change(
+    mut deps: DepsMut,
+    env: &Env,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    let mut response = Response::new();
+    match change {
+        ConfigChange::Price(price) => {
+            STATE.update(deps.storage, |mut state| -> StdResult<_> {
+                state.sell_price = price;
+                Ok(state)
+            })?;
+        }
+        ConfigChange::Supply {
+            reserved_supply,
+            market_supply,
+        } => {
+            let mut state = STATE.load(deps.storage)?;
+            let market_grows = market_supply > state.total_market_supply;
+            state.total_market_supply = market_supply;
+            state.total_reserved_supply = reserved_supply;
+
+            // Check if new supply is ok
+            is_market_nft_available(&state)?;
+            is_reserved_nft_available(&state)?;
+
+            STATE.save(deps.storage, &state)?;
+
+            // New market supply goes to the waitlist first, FulfillWaitlist serves the rest
+            if market_grows {
+                response = fulfill_waitlist(&mut deps, env, WAITLIST_FULFILL_LIMIT)?;
+            }
+        }
+        ConfigChange::NftContract(address) => {
+            is_nft_contract_free(&deps, None, &address)?;
+            NFT_CONTRACT.save(deps.storage, &address)?;
+        }
+        ConfigChange::AddTreasuryWallet(wallet) => {
+            let mut wallets = TREASURY_WALLETS.load(deps.storage)?;
+            if wallets.contains(&wallet) {
+                return Err(ContractError::AddressAlreadyRegistered {
+                    address: wallet.to_string(),
+                });
+            }
+            wallets.insert(wallet);
+            TREASURY_WALLETS.save(deps.storage, &wallets)?;
+        }
+        ConfigChange::RemoveTreasuryWallet(wallet) => {
+            let mut wallets = TREASURY_WALLETS.load(deps.storage)?;
+            if !wallets.remove(&wallet) {
+                return Err(ContractError::AddressNotFound {
+                    address: wallet.to_string(),
+                });
+            }
+            TREASURY_WALLETS.save(deps.storage, &wallets)?;
+        }
+        ConfigChange::TimelockDelay(delay) => TIMELOCK_DELAY.save(deps.storage, &delay)?,
+        ConfigChange::PermitSigner(pubkey) => PERMIT_SIGNER.save(deps.storage, &pubkey)?,
+        ConfigChange::Oracle(oracle) => ORACLE.save(deps.storage, &oracle)?,
+        ConfigChange::Product {
+            product_id,
+            mut product,
+        } => {
+            product.minted = match PRODUCTS.may_load(deps.storage, product_id)? {
+                Some(current) => current.minted,
+                None => 0,
+            };
+
+            // Check if new supply is ok
+            is_product_available(&product)?;
+
+            PRODUCTS.save(deps.storage, product_id, &product)?;
+        }
+        ConfigChange::Collection {
+            collection_id,
+            collection,
+        } => {
+            is_nft_contract_free(&deps, Some(collection_id), &collection.nft_contract)?;
+
+            let collection = match COLLECTIONS.may_load(deps.storage, collection_id)? {
+                Some(current) => {
+                    // Token ids are numbered per NFT contract
+                    if current.minted > 0 && current.nft_contract != collection.nft_contract {
+                        return Err(ContractError::CollectionNftContractLocked {});
+                    }
+                    Collection {
+                        minted: current.minted,
+                        last_token_id: current.last_token_id,
+                        revenue: current.revenue,
+                        ..collection
+                    }
+                }
+                None => collection,
+            };
+
+            // Check if new supply is ok
+            is_collection_available(&collection)?;
+
+            COLLECTIONS.save(deps.storage, collection_id, &collection)?;
+        }
+        ConfigChange::Plots(plots) => {
+            let mut state = STATE.load(deps.storage)?;
+            for entry in plots {
+                let token_id = entry.token_id;
+                match PLOTS.may_load(deps.storage, token_id)? {
+                    Some(plot) if plot.sold => return Err(ContractError::PlotSold { token_id }),
+                    Some(_) => {}
+                    None => {
+                        // Sequential ids below last_token_id are already minted
+                        if token_id == 0 || token_id <= state.last_token_id {
+                            return Err(ContractError::PlotIdTaken { token_id });
+                        }
+                        state.total_market_minted += 1;
+                    }
+                }
+                PLOTS.save(
+                    deps.storage,
+                    token_id,
+                    &Plot {
+                        price: entry.price,
+                        metadata: entry.metadata,
+                        sold: false,
+                    },
+                )?;
+            }
+
+            // Plots are taken from the market supply
+            is_market_nft_available(&state)?;
+            STATE.save(deps.storage, &state)?;
+        }
+        ConfigChange::Round { round_id, config } => {
+            is_round_closed(&deps, round_id)?;
+
+            ROUNDS.save(deps.storage, round_id, &config)?;
+            if !ROUND_STATS.has(deps.storage, round_id) {
+                ROUND_STATS.save(deps.storage, round_id, &RoundStats::default())?;
+            }
+        }
+        ConfigChange::MoveSupply { from, to, amount } => {
+            let mut state = STATE.load(deps.storage)?;
+            let (from_supply, to_supply) = match (from, to) {
+                (SupplyKind::Reserved, SupplyKind::Market) => (
+                    &mut state.total_reserved_supply,
+                    &mut state.total_market_supply,
+                ),
+                (SupplyKind::Market, SupplyKind::Reserved) => (
+                    &mut state.total_market_supply,
+                    &mut state.total_reserved_supply,
+                ),
+                _ => return Err(ContractError::InvalidSupplyMove {}),
+            };
+            *from_supply = match from_supply.checked_sub(amount) {
+                Some(supply) => supply,
+                None => return Err(ContractError::NotEnoughNftLeft {}),
+            };
+            *to_supply += amount;
+
+            // Minted tokens can't be moved
+            is_market_nft_available(&state)?;
+            is_reserved_nft_available(&state)?;
+
+            STATE.save(deps.storage, &state)?;
+
+            // New market supply goes to the waitlist first, FulfillWaitlist serves the rest
+            if to == SupplyKind::Market {
+                response = fulfill_waitlist(&mut deps, env, WAITLIST_FULFILL_LIMIT)?;
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn try_sell_mode(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    enable: bool,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let mut response = Response::new();
+
+    // Closing the public sale releases the unminted reserved supply, through the timelock
+    if !enable && SELL_MODE.load(deps.storage)? && AUTO_RELEASE_RESERVED.load(deps.storage)? {
+        let state = STATE.load(deps.storage)?;
+        let released = state.total_reserved_supply - state.total_reserved_minted;
+        if released > 0 {
+            response = schedule_change(
+                deps.branch(),
+                &env,
+                ConfigChange::MoveSupply {
+                    from: SupplyKind::Reserved,
+                    to: SupplyKind::Market,
+                    amount: released,
+                },
+            )?;
+        }
+
+        response = response.add_attribute("released_reserved", released.to_string());
+    }
+
+    SELL_MODE.save(deps.storage, &enable)?;
+
+    Ok(response.add_attribute("method", "try_sell_mode"))
+}
+
+pub fn try_move_supply(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: SupplyKind,
+    to: SupplyKind,
+    amount: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if from == to || amount == 0 {
+        return Err(ContractError::InvalidSupplyMove {});
+    }
+
+    let response = schedule_change(deps, &env, ConfigChange::MoveSupply { from, to, amount })?;
+
+    Ok(response.add_attribute("method", "try_move_supply"))
+}
+
+pub fn update_auto_release_reserved(
+    deps: DepsMut,
+    info: MessageInfo,
+    enable: bool,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    AUTO_RELEASE_RESERVED.save(deps.storage, &enable)?;
+
+    Ok(Response::new().add_attribute("method", "update_auto_release_reserved"))
+}
+
+pub fn try_pre_sell_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    enable: bool,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    PRE_SELL_MODE.save(deps.storage, &enable)?;
+
+    Ok(Response::new().add_attribute("method", "try_pre_sell_mode"))
+}
+
+pub fn try_update_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    entries: Vec<WhiteListEntry>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // Update contract state
+    for authorized in entries {
+        match deps.api.addr_validate(&authorized.address) {
+            Ok(addr) => WHITELIST.save(deps.storage, addr, &authorized.nb_slots)?,
+            Err(_e) => {
+                return Err(ContractError::InvalidAddress {
+                    address: authorized.address,
+                })
+            }
+        }
+    }
+
+    Ok(Response::new().add_attribute("method", "update_price"))
+}
+
+pub fn set_permit_signer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    // Is owner wallet ?
+    is_owner(&deps, &info)?;
+
+    // compressed or uncompressed secp256k1 public key
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidPublicKey {});
+    }
+
+    let response = schedule_change(deps, &env, ConfigChange::PermitSigner(pubkey))?;
+
+    Ok(response.add_attribute("method", "set_permit_signer"))
+}
+
+pub fn update_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price: Coin,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let response = schedule_change(deps, &env, ConfigChange::Price(price))?;
+
+    Ok(response.add_attribute("method", "update_price"))
+}
+
+pub fn update_supply(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reserved_supply: u32,
+    market_supply: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let response = schedule_change(
+        deps,
+        &env,
+        ConfigChange::Supply {
+            reserved_supply,
+            market_supply,
+        },
+    )?;
+
+    Ok(response.add_attribute("method", "update_price"))
+}
+
+pub fn remove_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Admins approve withdrawals, only the owner manages them
+    is_owner(&deps, &info)?;
+
+    // check validity of new admin address
+    let admin_to_remove = deps.api.addr_validate(address.as_str());
+    if admin_to_remove.is_err() {
+        return Err(ContractError::InvalidAddress { address });
+    }
+
+    // check if new admin
+    if !ADMIN_WALLETS
+        .load(deps.storage)?
+        .contains(admin_to_remove.as_ref().unwrap())
+    {
+        return Err(ContractError::AddressNotFound { address });
+    }
+
+    // The owner and the remaining admins must still reach the withdraw threshold
+    let nb_approvers = ADMIN_WALLETS.load(deps.storage)?.len() as u32;
+    if nb_approvers < WITHDRAW_CONFIG.load(deps.storage)?.threshold {
+        return Err(ContractError::InvalidWithdrawThreshold {});
+    }
+
+    ADMIN_WALLETS.update(
+        deps.storage,
+        |mut wallets| -> Result<HashSet<Addr>, ContractError> {
+            wallets.remove(&admin_to_remove.unwrap());
+            Ok(wallets)
+        },
+    )?;
+
+    let response = Response::new();
+    Ok(response.add_attribute("method", "remove_admin"))
+}
+
+pub fn add_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Admins approve withdrawals, only the owner manages them
+    is_owner(&deps, &info)?;
+
+    // check validity of new admin address
+    let admin_to_add = deps.api.addr_validate(address.as_str());
+    if admin_to_add.is_err() {
+        return Err(ContractError::InvalidAddress { address });
+    }
+
+    // check if new admin
+    if ADMIN_WALLETS
+        .load(deps.storage)?
+        .contains(admin_to_add.as_ref().unwrap())
+    {
+        return Err(ContractError::AddressAlreadyRegistered { address });
+    }
+
+    ADMIN_WALLETS.update(
+        deps.storage,
+        |mut wallets| -> Result<HashSet<Addr>, ContractError> {
+            wallets.insert(admin_to_add.unwrap());
+            Ok(wallets)
+        },
+    )?;
+
+    let response = Response::new();
+    Ok(response.add_attribute("method", "add_admin"))
+}
+
+fn mint_helper(nft_id: u32, addr: String, metadata: Metadata) -> cw_carbonable_lib::ExecuteMsg {
+    let root_token_uri = metadata.external_url.clone().unwrap_or("".to_string());
+    let token_uri = format!("{}{}", root_token_uri, nft_id);
+    cw_carbonable_lib::ExecuteMsg::Mint(cw721_base::MintMsg::<Extension> {
+        token_id: nft_id.to_string(),
+        owner: addr,
+        token_uri: Some(token_uri),
+        extension: Extension::from(metadata),
+    })
+}
+
+/// Deterministic pseudo-random number
+///
+/// First 8 bytes (big endian) of sha256(block height | block time in nanos | sender | token id),
+/// integers being encoded big endian. Anybody can predict it before the transaction is
+/// included: it only spreads templates, it must not protect anything valuable.
+pub fn pseudo_random(env: &Env, sender: &Addr, nft_id: u32) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(env.block.height.to_be_bytes());
+    hasher.update(env.block.time.nanos().to_be_bytes());
+    hasher.update(sender.as_bytes());
+    hasher.update(nft_id.to_be_bytes());
+    let hash = hasher.finalize();
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[0..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Pick a metadata template for a token and burn one of its remaining slots
+///
+/// Returns None when no template is registered.
+fn pick_metadata_template(
+    deps: &mut DepsMut,
+    env: &Env,
+    sender: &Addr,
+    nft_id: u32,
+) -> Result<Option<Metadata>, ContractError> {
+    let templates = METADATA_TEMPLATES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<(u32, MetadataTemplate)>, _>>()?;
+    if templates.is_empty() {
+        return Ok(None);
+    }
+
+    let total_weight: u64 = templates
+        .iter()
+        .filter(|(_, template)| template.remaining > 0)
+        .map(|(_, template)| template.weight as u64)
+        .sum();
+    if total_weight == 0 {
+        return Err(ContractError::NoMetadataTemplateLeft {});
+    }
+
+    let mut target = pseudo_random(env, sender, nft_id) % total_weight;
+    for (template_id, mut template) in templates {
+        if template.remaining == 0 {
+            continue;
+        }
+        if target < template.weight as u64 {
+            template.remaining -= 1;
+            METADATA_TEMPLATES.save(deps.storage, template_id, &template)?;
+            return Ok(Some(template.metadata));
+        }
+        target -= template.weight as u64;
+    }
+
+    Err(ContractError::NoMetadataTemplateLeft {})
+}
+
+/// Build the metadata of a token: base metadata + generated attributes
+fn token_metadata(
+    deps: &DepsMut,
+    env: &Env,
+    edition_size: u32,
+    mut metadata: Metadata,
+    nft_id: u32,
+    phase: MintPhase,
+) -> Result<Metadata, ContractError> {
+    let generated = GENERATED_ATTRIBUTES.load(deps.storage)?;
+    if generated.is_empty() {
+        return Ok(metadata);
+    }
+
+    let mut attributes = metadata.attributes.unwrap_or_default();
+    for attribute in generated {
+        attributes.push(match attribute {
+            GeneratedAttribute::SerialNumber => Trait {
+                display_type: Some(String::from("number")),
+                trait_type: String::from("serial_number"),
+                value: nft_id.to_string(),
+            },
+            GeneratedAttribute::Edition => Trait {
+                display_type: None,
+                trait_type: String::from("edition"),
+                value: format!("{} of {}", nft_id, edition_size),
+            },
+            GeneratedAttribute::MintPhase => Trait {
+                display_type: None,
+                trait_type: String::from("mint_phase"),
+                value: phase.as_str().to_string(),
+            },
+            GeneratedAttribute::MintDate => Trait {
+                display_type: Some(String::from("date")),
+                trait_type: String::from("mint_date"),
+                value: env.block.time.seconds().to_string(),
+            },
+            GeneratedAttribute::BuyerType => Trait {
+                display_type: None,
+                trait_type: String::from("buyer_type"),
+                value: phase.buyer_type().to_string(),
+            },
+        });
+    }
+    metadata.attributes = Some(attributes);
+
+    Ok(metadata)
+}
+
+pub fn is_market_nft_available(state: &State) -> Result<(), ContractError> {
+    if state.total_market_minted > state.total_market_supply {
+        return Err(ContractError::NotEnoughNftLeft {});
+    }
+
+    Ok(())
+}
+
+/// The waitlist has priority on the market supply, its first entry must be fulfilled first
+fn is_waitlist_served(deps: &DepsMut, state: &State) -> Result<(), ContractError> {
+    let first = WAITLIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    if let Some((_, entry)) = first {
+        if state.total_market_minted + entry.quantity <= state.total_market_supply {
+            return Err(ContractError::WaitlistPending {});
+        }
+    }
+
+    Ok(())
+}
+
+pub fn is_collection_available(collection: &Collection) -> Result<(), ContractError> {
+    if collection.minted > collection.supply {
+        return Err(ContractError::NotEnoughNftLeft {});
+    }
+
+    Ok(())
+}
+
+pub fn is_product_available(product: &Product) -> Result<(), ContractError> {
+    if product.minted > product.supply {
+        return Err(ContractError::NotEnoughNftLeft {});
+    }
+
+    Ok(())
+}
+
+pub fn is_reserved_nft_available(state: &State) -> Result<(), ContractError> {
+    if state.total_reserved_minted > state.total_reserved_supply {
+        return Err(ContractError::NotEnoughNftLeft {});
+    }
+
+    Ok(())
+}
+
+pub fn is_multi_buy_quantity_too_big(quantity: u32, state: &State) -> Result<(), ContractError> {
+    if quantity > state.max_buy_at_once {
+        return Err(ContractError::MultiBuyQuantityTooHigh {});
+    }
+
+    Ok(())
+}
+
+/// Phase of the running sale
+pub fn sell_phase(deps: &DepsMut) -> Result<MintPhase, ContractError> {
+    if SELL_MODE.load(deps.storage)? {
+        return Ok(MintPhase::PublicSale);
+    }
+
+    if PRE_SELL_MODE.load(deps.storage)? {
+        return Ok(MintPhase::PreSale);
+    }
+
+    Err(ContractError::SellClose {})
+}
+
+pub fn set_collection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection_id: u32,
+    config: CollectionConfig,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let nft_contract = match deps.api.addr_validate(&config.nft_contract) {
+        Ok(addr) => addr,
+        Err(_) => {
+            return Err(ContractError::InvalidAddress {
+                address: config.nft_contract,
+            })
+        }
+    };
+
+    is_nft_contract_free(&deps, Some(collection_id), &nft_contract)?;
+
+    // Collection prices and supplies are timelocked like the sell price and supply
+    let response = schedule_change(
+        deps,
+        &env,
+        ConfigChange::Collection {
+            collection_id,
+            collection: Collection {
+                name: config.name,
+                nft_contract,
+                price: config.price,
+                supply: config.supply,
+                metadata: config.metadata,
+                minted: 0,
+                last_token_id: 0,
+                revenue: vec![],
+            },
+        },
+    )?;
+
+    Ok(response.add_attribute("method", "set_collection"))
+}
+
+/// Check an NFT contract is neither the main NFT contract nor the one of another collection
+///
+/// Token ids are numbered per sale, two sales minting on the same contract would collide.
+fn is_nft_contract_free(
+    deps: &DepsMut,
+    collection_id: Option<u32>,
+    nft_contract: &Addr,
+) -> Result<(), ContractError> {
+    let mut used = collection_id.is_some() && NFT_CONTRACT.load(deps.storage)? == *nft_contract;
+    for item in COLLECTIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (id, collection) = item?;
+        used |= Some(id) != collection_id && collection.nft_contract == *nft_contract;
+    }
+
+    if used {
+        return Err(ContractError::NftContractInUse {
+            address: nft_contract.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn set_product(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    product_id: u32,
+    product: Product,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // Product prices and supplies replace the sell price and supply, they are timelocked too
+    let response = schedule_change(
+        deps,
+        &env,
+        ConfigChange::Product {
+            product_id,
+            product,
+        },
+    )?;
+
+    Ok(response.add_attribute("method", "set_product"))
+}
+
+pub fn remove_product(
+    deps: DepsMut,
+    info: MessageInfo,
+    product_id: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if !PRODUCTS.has(deps.storage, product_id) {
+        return Err(ContractError::ProductNotFound { product_id });
+    }
+    PRODUCTS.remove(deps.storage, product_id);
+
+    Ok(Response::new().add_attribute("method", "remove_product"))
+}
+
+pub fn set_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u32,
+    config: RoundConfig,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if config.start >= config.end {
+        return Err(ContractError::InvalidRound {});
+    }
+    is_round_closed(&deps, round_id)?;
+
+    // Round prices and supplies replace the sell price and supply, they are timelocked too
+    let response = schedule_change(deps, &env, ConfigChange::Round { round_id, config })?;
+
+    Ok(response.add_attribute("method", "set_round"))
+}
+
+pub fn remove_round(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if !ROUNDS.has(deps.storage, round_id) {
+        return Err(ContractError::RoundNotFound { round_id });
+    }
+    is_round_closed(&deps, round_id)?;
+
+    ROUNDS.remove(deps.storage, round_id);
+    ROUND_STATS.remove(deps.storage, round_id);
+    let whitelist: Vec<Addr> = ROUND_WHITELIST
+        .prefix(round_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for address in whitelist {
+        ROUND_WHITELIST.remove(deps.storage, (round_id, address));
+    }
+
+    if let Some(current) = CURRENT_ROUND.load(deps.storage)? {
+        if current.round_id == round_id {
+            CURRENT_ROUND.save(deps.storage, &None)?;
+        }
+    }
+
+    Ok(Response::new().add_attribute("method", "remove_round"))
+}
+
+pub fn add_to_round_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: u32,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if !ROUNDS.has(deps.storage, round_id) {
+        return Err(ContractError::RoundNotFound { round_id });
+    }
+
+    for address in addresses {
+        match deps.api.addr_validate(&address) {
+            Ok(addr) => ROUND_WHITELIST.save(deps.storage, (round_id, addr), &true)?,
+            Err(_) => return Err(ContractError::InvalidAddress { address }),
+        }
+    }
+
+    Ok(Response::new().add_attribute("method", "add_to_round_whitelist"))
+}
+
+pub fn activate_round(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let mut stats = match ROUND_STATS.may_load(deps.storage, round_id)? {
+        Some(stats) => stats,
+        None => return Err(ContractError::RoundNotFound { round_id }),
+    };
+
+    let mut response = Response::new();
+
+    // Carry the unsold supply of the previous round
+    if let Some(previous) = CURRENT_ROUND.load(deps.storage)? {
+        let previous_config = ROUNDS.load(deps.storage, previous.round_id)?;
+        if previous.round_id != round_id && previous_config.carry_forward {
+            let mut previous_stats = ROUND_STATS.load(deps.storage, previous.round_id)?;
+            let unsold = previous_stats.remaining(&previous_config);
+            previous_stats.carried_out += unsold;
+            stats.carried_in += unsold;
+            ROUND_STATS.save(deps.storage, previous.round_id, &previous_stats)?;
+            ROUND_STATS.save(deps.storage, round_id, &stats)?;
+
+            response = response.add_attribute("carried_forward", unsold.to_string());
+        }
+    }
+
+    CURRENT_ROUND.save(
+        deps.storage,
+        &Some(CurrentRound {
+            round_id,
+            open: true,
+        }),
+    )?;
+
+    Ok(response.add_attribute("method", "activate_round"))
+}
+
+pub fn close_round(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    CURRENT_ROUND.update(deps.storage, |current| -> StdResult<_> {
+        Ok(current.map(|current| CurrentRound {
+            round_id: current.round_id,
+            open: false,
+        }))
+    })?;
+
+    Ok(Response::new().add_attribute("method", "close_round"))
+}
+
+fn is_round_closed(deps: &DepsMut, round_id: u32) -> Result<(), ContractError> {
+    if let Some(CurrentRound {
+        round_id: id,
+        open: true,
+    }) = CURRENT_ROUND.load(deps.storage)?
+    {
+        if id == round_id {
+            return Err(ContractError::RoundOpen { round_id });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a purchase against the open round and count it
+///
+/// Returns the mint phase and the unit price of the round.
+fn use_round(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    round_id: u32,
+    quantity: u32,
+) -> Result<(MintPhase, Coin), ContractError> {
+    let config = ROUNDS.load(deps.storage, round_id)?;
+    let now = env.block.time.seconds();
+    if now < config.start {
+        return Err(ContractError::RoundNotStarted {});
+    }
+    if now >= config.end {
+        return Err(ContractError::RoundEnded {});
+    }
+
+    if config.whitelist_only && !ROUND_WHITELIST.has(deps.storage, (round_id, info.sender.clone()))
+    {
+        return Err(ContractError::AddressNotWhitelisted {});
+    }
+
+    let bought = ROUND_PURCHASES.may_load(deps.storage, (round_id, info.sender.clone()))?;
+    let total = bought.unwrap_or(0) + quantity;
+    if let Some(max_per_wallet) = config.max_per_wallet {
+        if total > max_per_wallet {
+            return Err(ContractError::RoundWalletLimitReached {});
+        }
+    }
+    ROUND_PURCHASES.save(deps.storage, (round_id, info.sender.clone()), &total)?;
+
+    let mut stats = ROUND_STATS.load(deps.storage, round_id)?;
+    if stats.remaining(&config) < quantity {
+        return Err(ContractError::NotEnoughNftLeft {});
+    }
+    stats.nb_tokens += quantity;
+    if bought.is_none() {
+        stats.nb_buyers += 1;
+    }
+    ROUND_STATS.save(deps.storage, round_id, &stats)?;
+
+    let phase = match config.whitelist_only {
+        true => MintPhase::PreSale,
+        false => MintPhase::PublicSale,
+    };
+
+    Ok((phase, config.price))
+}
+
+/// Check a purchase permit and burn its nonce
+///
+/// Returns the unit price granted by the permit.
+pub fn use_permit(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    permit: Permit,
+    nb_to_buy: u32,
+    product_id: Option<u32>,
+    collection_id: Option<u32>,
+) -> Result<Coin, ContractError> {
+    let pubkey = match PERMIT_SIGNER.may_load(deps.storage)? {
+        Some(pubkey) => pubkey,
+        None => return Err(ContractError::PermitSignerNotSet {}),
+    };
+
+    if permit.buyer != info.sender.as_str() {
+        return Err(ContractError::InvalidPermit {});
+    }
+
+    if env.block.time.seconds() > permit.expires {
+        return Err(ContractError::PermitExpired {});
+    }
+
+    if nb_to_buy > permit.max_quantity {
+        return Err(ContractError::PermitQuantityExceeded {});
+    }
+
+    if USED_PERMIT_NONCES.has(deps.storage, permit.nonce) {
+        return Err(ContractError::PermitNonceUsed {
+            nonce: permit.nonce,
+        });
+    }
+
+    let payload = PermitPayload {
+        contract: env.contract.address.to_string(),
+        buyer: permit.buyer,
+        max_quantity: permit.max_quantity,
+        price: permit.price,
+        expires: permit.expires,
+        nonce: permit.nonce,
+        product_id,
+        collection_id,
+    };
+    let hash = Sha256::digest(to_json_vec(&payload)?);
+    let is_signature_valid = deps
+        .api
+        .secp256k1_verify(&hash, &permit.signature, &pubkey)
+        .unwrap_or(false);
+    if !is_signature_valid {
+        return Err(ContractError::InvalidPermitSignature {});
+    }
+
+    // burn nonce
+    USED_PERMIT_NONCES.save(deps.storage, payload.nonce, &true)?;
+
+    Ok(payload.price)
+}
+
+pub fn create_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: AuctionConfig,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if config.start >= config.end || config.min_increment.is_zero() {
+        return Err(ContractError::InvalidAuction {});
+    }
+
+    // The auctioned token is taken from the market supply
+    let mut state = STATE.load(deps.storage)?;
+    state.total_market_minted += 1;
+    is_market_nft_available(&state)?;
+    STATE.save(deps.storage, &state)?;
+
+    let auction_id = AUCTION_COUNT.load(deps.storage)? + 1;
+    AUCTION_COUNT.save(deps.storage, &auction_id)?;
+    AUCTIONS.save(
+        deps.storage,
+        auction_id,
+        &Auction {
+            end: config.end,
+            config,
+            highest_bid: None,
+            nb_bids: 0,
+            settled: false,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "create_auction")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+pub fn cancel_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let auction = load_auction(&deps, auction_id)?;
+    if auction.settled {
+        return Err(ContractError::AuctionEnded {});
+    }
+    if auction.highest_bid.is_some() {
+        return Err(ContractError::AuctionHasBids {});
+    }
+
+    // Give the token back to the market supply
+    let mut state = STATE.load(deps.storage)?;
+    state.total_market_minted -= 1;
+    STATE.save(deps.storage, &state)?;
+
+    AUCTIONS.remove(deps.storage, auction_id);
+
+    Ok(Response::new().add_attribute("method", "cancel_auction"))
+}
+
+pub fn try_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = load_auction(&deps, auction_id)?;
+
+    let now = env.block.time.seconds();
+    if now < auction.config.start {
+        return Err(ContractError::AuctionNotStarted {});
+    }
+    if auction.settled || now >= auction.end {
+        return Err(ContractError::AuctionEnded {});
+    }
+
+    let denom = auction.config.reserve_price.denom.clone();
+    let min_bid = match &auction.highest_bid {
+        Some(bid) => Coin {
+            denom: denom.clone(),
+            amount: bid.amount.amount + auction.config.min_increment,
+        },
+        None => auction.config.reserve_price.clone(),
+    };
+    let amount = match info.funds.as_slice() {
+        [c] if c.denom == denom => c.clone(),
+        _ => return Err(ContractError::InvalidBidFunds {}),
+    };
+    if amount.amount < min_bid.amount {
+        return Err(ContractError::BidTooLow { min_bid });
+    }
+
+    let bid = Bid {
+        bidder: info.sender,
+        amount,
+        time: now,
+    };
+
+    // Refund the outbid bidder
+    escrow(deps.storage, &bid.amount)?;
+    let mut response = Response::new();
+    if let Some(outbid) = auction.highest_bid.replace(bid.clone()) {
+        release(deps.storage, &outbid.amount)?;
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: outbid.bidder.to_string(),
+                amount: vec![outbid.amount],
+            })
+            .add_attribute("refunded", outbid.bidder.to_string());
+    }
+
+    // Anti-sniping, a late bid pushes the end back
+    if now + auction.config.extension > auction.end {
+        auction.end = now + auction.config.extension;
+    }
+    auction.nb_bids += 1;
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    AUCTION_BIDS.save(deps.storage, (auction_id, auction.nb_bids), &bid)?;
+
+    Ok(response
+        .add_attribute("method", "try_place_bid")
+        .add_attribute("end", auction.end.to_string()))
+}
+
+pub fn try_settle_auction(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = load_auction(&deps, auction_id)?;
+    if auction.settled {
+        return Err(ContractError::AuctionEnded {});
+    }
+    if env.block.time.seconds() < auction.end {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+    auction.settled = true;
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    let mut state = STATE.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("method", "try_settle_auction");
+
+    match auction.highest_bid {
+        Some(bid) => {
+            release(deps.storage, &bid.amount)?;
+            record_sale(
+                &mut deps,
+                &env,
+                &bid.bidder,
+                MintPhase::PublicSale,
+                1,
+                std::slice::from_ref(&bid.amount),
+            )?;
+
+            next_token_id(&deps, &mut state);
+            let metadata = token_metadata(
+                &deps,
+                &env,
+                state.total_market_supply + state.total_reserved_supply,
+                auction.config.metadata,
+                state.last_token_id,
+                MintPhase::PublicSale,
+            )?;
+            response = response
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+                    msg: to_json_binary(&mint_helper(
+                        state.last_token_id,
+                        bid.bidder.to_string(),
+                        metadata,
+                    ))?,
+                    funds: vec![],
+                }))
+                .add_attribute("winner", bid.bidder.to_string());
+        }
+        // No bid, give the token back to the market supply
+        None => state.total_market_minted -= 1,
+    }
+    STATE.save(deps.storage, &state)?;
+
+    Ok(response)
+}
+
+fn load_auction(deps: &DepsMut, auction_id: u64) -> Result<Auction, ContractError> {
+    match AUCTIONS.may_load(deps.storage, auction_id)? {
+        Some(auction) => Ok(auction),
+        None => Err(ContractError::AuctionNotFound { auction_id }),
+    }
+}
+
+pub fn create_sealed_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: SealedAuctionConfig,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    if config.quantity == 0
+        || config.commit_start >= config.commit_end
+        || config.commit_end >= config.reveal_end
+    {
+        return Err(ContractError::InvalidAuction {});
+    }
+
+    // The auctioned tokens are taken from the market supply
+    let mut state = STATE.load(deps.storage)?;
+    state.total_market_minted += config.quantity;
+    is_market_nft_available(&state)?;
+    STATE.save(deps.storage, &state)?;
+
+    let auction_id = AUCTION_COUNT.load(deps.storage)? + 1;
+    AUCTION_COUNT.save(deps.storage, &auction_id)?;
+    SEALED_AUCTIONS.save(
+        deps.storage,
+        auction_id,
+        &SealedAuction {
+            config,
+            nb_commitments: 0,
+            nb_reveals: 0,
+            winners: vec![],
+            nb_settled: 0,
+            clearing_price: None,
+            settled: false,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "create_sealed_auction")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+pub fn try_commit_sealed_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    let mut auction = load_sealed_auction(&deps, auction_id)?;
+
+    let now = env.block.time.seconds();
+    if now < auction.config.commit_start {
+        return Err(ContractError::AuctionNotStarted {});
+    }
+    if now >= auction.config.commit_end {
+        return Err(ContractError::CommitPhaseEnded {});
+    }
+    is_hash_valid(&commitment)?;
+
+    if SEALED_BIDS.has(deps.storage, (auction_id, info.sender.clone())) {
+        return Err(ContractError::SealedBidAlreadyCommitted {});
+    }
+
+    // The deposit covers at least the reserve price
+    let deposit = match info
+        .funds
+        .iter()
+        .find(|c| c.denom == auction.config.denom && c.amount >= auction.config.reserve_price)
+    {
+        Some(c) => c.clone(),
+        None => return Err(ContractError::InsufficientDeposit {}),
+    };
+
+    escrow(deps.storage, &deposit)?;
+    SEALED_BIDS.save(
+        deps.storage,
+        (auction_id, info.sender),
+        &SealedBid {
+            commitment,
+            deposit,
+            committed: now,
+            price: None,
+        },
+    )?;
+    auction.nb_commitments += 1;
+    SEALED_AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new().add_attribute("method", "try_commit_sealed_bid"))
+}
+
+pub fn try_reveal_sealed_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    price: Uint128,
+    salt: String,
+) -> Result<Response, ContractError> {
+    let mut auction = load_sealed_auction(&deps, auction_id)?;
+
+    let now = env.block.time.seconds();
+    if now < auction.config.commit_end {
+        return Err(ContractError::RevealPhaseNotStarted {});
+    }
+    if now >= auction.config.reveal_end {
+        return Err(ContractError::AuctionEnded {});
+    }
+
+    let mut bid = match SEALED_BIDS.may_load(deps.storage, (auction_id, info.sender.clone()))? {
+        Some(bid) if bid.price.is_none() => bid,
+        _ => return Err(ContractError::SealedBidNotFound {}),
+    };
+
+    let payload = SealedBidPayload {
+        contract: env.contract.address.to_string(),
+        auction_id,
+        bidder: info.sender.to_string(),
+        price,
+        salt,
+    };
+    if hex::encode(Sha256::digest(to_json_vec(&payload)?)) != bid.commitment {
+        return Err(ContractError::SealedBidMismatch {});
+    }
+    if price < auction.config.reserve_price {
+        return Err(ContractError::BidTooLow {
+            min_bid: Coin {
+                denom: auction.config.denom,
+                amount: auction.config.reserve_price,
+            },
+        });
+    }
+    if price > bid.deposit.amount {
+        return Err(ContractError::InsufficientDeposit {});
+    }
+
+    // Keep the best revealed bids, the earliest commitment wins a tie
+    let position = auction
+        .winners
+        .iter()
+        .position(|w| price > w.price || (price == w.price && bid.committed < w.committed))
+        .unwrap_or(auction.winners.len());
+    auction.winners.insert(
+        position,
+        SealedWinner {
+            bidder: info.sender.clone(),
+            price,
+            committed: bid.committed,
+        },
+    );
+    auction.winners.truncate(auction.config.quantity as usize);
+
+    bid.price = Some(price);
+    SEALED_BIDS.save(deps.storage, (auction_id, info.sender), &bid)?;
+    auction.nb_reveals += 1;
+    SEALED_AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new().add_attribute("method", "try_reveal_sealed_bid"))
+}
+
+/// Maximum sealed auction winners settled by a call
+const SEALED_SETTLE_LIMIT: u32 = 10;
+
+pub fn try_settle_sealed_auction(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut auction = load_sealed_auction(&deps, auction_id)?;
+    if auction.settled {
+        return Err(ContractError::AuctionEnded {});
+    }
+    if env.block.time.seconds() < auction.config.reveal_end {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    let mut state = STATE.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("method", "try_settle_sealed_auction");
+
+    // Uniform clearing price, the lowest winning bid, unsold tokens go back to the market supply
+    let clearing_price = match auction.clearing_price.clone() {
+        Some(clearing_price) => clearing_price,
+        None => {
+            state.total_market_minted -= auction.config.quantity - auction.winners.len() as u32;
+
+            let clearing_price = Coin {
+                denom: auction.config.denom.clone(),
+                amount: auction
+                    .winners
+                    .last()
+                    .map(|winner| winner.price)
+                    .unwrap_or_default(),
+            };
+            if !auction.winners.is_empty() {
+                response = response.add_attribute("clearing_price", clearing_price.to_string());
+                auction.clearing_price = Some(clearing_price.clone());
+            }
+            clearing_price
+        }
+    };
+
+    let limit = limit
+        .unwrap_or(SEALED_SETTLE_LIMIT)
+        .min(SEALED_SETTLE_LIMIT) as usize;
+    let winners: Vec<SealedWinner> = auction
+        .winners
+        .iter()
+        .skip(auction.nb_settled as usize)
+        .take(limit)
+        .cloned()
+        .collect();
+    for winner in winners {
+        let bid = SEALED_BIDS.load(deps.storage, (auction_id, winner.bidder.clone()))?;
+        SEALED_BIDS.remove(deps.storage, (auction_id, winner.bidder.clone()));
+        release(deps.storage, &bid.deposit)?;
+
+        record_sale(
+            &mut deps,
+            &env,
+            &winner.bidder,
+            MintPhase::PublicSale,
+            1,
+            std::slice::from_ref(&clearing_price),
+        )?;
+
+        next_token_id(&deps, &mut state);
+        let metadata = token_metadata(
+            &deps,
+            &env,
+            state.total_market_supply + state.total_reserved_supply,
+            auction.config.metadata.clone(),
+            state.last_token_id,
+            MintPhase::PublicSale,
+        )?;
+        response = response
+            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+                msg: to_json_binary(&mint_helper(
+                    state.last_token_id,
+                    winner.bidder.to_string(),
+                    metadata,
+                ))?,
+                funds: vec![],
+            }))
+            .add_attribute("winner", winner.bidder.to_string());
+
+        let mut refund = bid.deposit;
+        refund.amount -= clearing_price.amount;
+        if !refund.amount.is_zero() {
+            response = response.add_message(BankMsg::Send {
+                to_address: winner.bidder.to_string(),
+                amount: vec![refund],
+            });
+        }
+        auction.nb_settled += 1;
+    }
+    STATE.save(deps.storage, &state)?;
+
+    auction.settled = auction.nb_settled as usize == auction.winners.len();
+    SEALED_AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(response)
+}
+
+/// Refund the deposit of a losing or unrevealed bid once the reveal phase is over
+pub fn try_claim_sealed_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = load_sealed_auction(&deps, auction_id)?;
+    if env.block.time.seconds() < auction.config.reveal_end {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    let bid = match SEALED_BIDS.may_load(deps.storage, (auction_id, info.sender.clone()))? {
+        Some(bid) if !auction.winners.iter().any(|w| w.bidder == info.sender) => bid,
+        _ => return Err(ContractError::SealedBidNotFound {}),
+    };
+    SEALED_BIDS.remove(deps.storage, (auction_id, info.sender.clone()));
+    release(deps.storage, &bid.deposit)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![bid.deposit],
+        })
+        .add_attribute("method", "try_claim_sealed_refund"))
+}
+
+fn load_sealed_auction(deps: &DepsMut, auction_id: u64) -> Result<SealedAuction, ContractError> {
+    match SEALED_AUCTIONS.may_load(deps.storage, auction_id)? {
+        Some(auction) => Ok(auction),
+        None => Err(ContractError::AuctionNotFound { auction_id }),
+    }
+}
+
+/// Maximum number of lottery tickets, bounds the draw
+const MAX_LOTTERY_TICKETS: u32 = 1000;
+
+pub fn create_lottery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: LotteryConfig,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // A new lottery can be created once the current one is drawn or expired, and every entry
+    // has been claimed or the claim window has ended
+    let lottery_id = LOTTERY_COUNT.load(deps.storage)?;
+    if let Some(mut lottery) = LOTTERIES.may_load(deps.storage, lottery_id)? {
+        let now = env.block.time.seconds();
+        let over = lottery.seed.is_some() || now >= lottery.config.draw_deadline;
+        let claimed =
+            lottery.nb_claimed == lottery.nb_entrants || now >= lottery.config.claim_deadline;
+        if !over || !claimed {
+            return Err(ContractError::LotteryAlreadyCreated {});
+        }
+        close_lottery(deps.storage, &mut lottery)?;
+        LOTTERIES.save(deps.storage, lottery_id, &lottery)?;
+    }
+    if config.supply == 0
+        || config.registration_start >= config.registration_end
+        || config.registration_end >= config.draw_deadline
+        || config.draw_deadline >= config.claim_deadline
+    {
+        return Err(ContractError::InvalidLottery {});
+    }
+    is_hash_valid(&config.seed_hash)?;
+
+    // The lottery tokens are taken from the market supply
+    let mut state = STATE.load(deps.storage)?;
+    state.total_market_minted += config.supply;
+    is_market_nft_available(&state)?;
+    STATE.save(deps.storage, &state)?;
+
+    let lottery_id = lottery_id + 1;
+    LOTTERY_COUNT.save(deps.storage, &lottery_id)?;
+    LOTTERIES.save(
+        deps.storage,
+        lottery_id,
+        &Lottery {
+            config,
+            nb_entrants: 0,
+            nb_tickets: 0,
+            nb_winning_tickets: 0,
+            seed: None,
+            closed: false,
+            nb_claimed: 0,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "create_lottery")
+        .add_attribute("lottery_id", lottery_id.to_string()))
+}
+
+pub fn try_register_lottery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    quantity: u32,
+) -> Result<Response, ContractError> {
+    let lottery_id = LOTTERY_COUNT.load(deps.storage)?;
+    let mut lottery = load_lottery(&deps, lottery_id)?;
+
+    let now = env.block.time.seconds();
+    if now < lottery.config.registration_start || now >= lottery.config.registration_end {
+        return Err(ContractError::RegistrationNotOpen {});
+    }
+    if quantity == 0 {
+        return Err(ContractError::InvalidQuantity {});
+    }
+    is_multi_buy_quantity_too_big(quantity, &STATE.load(deps.storage)?)?;
+    if lottery.nb_tickets + quantity > MAX_LOTTERY_TICKETS {
+        return Err(ContractError::LotteryFull {});
+    }
+
+    if LOTTERY_ENTRIES.has(deps.storage, (lottery_id, info.sender.clone())) {
+        return Err(ContractError::LotteryAlreadyRegistered {});
+    }
+
+    // Whitelist slots are burnt, the ones not won are given back on claim
+    let nb_slot = match WHITELIST.may_load(deps.storage, info.sender.clone())? {
+        Some(nb_slot) => nb_slot,
+        None => return Err(ContractError::AddressNotWhitelisted {}),
+    };
+    if nb_slot < quantity {
+        return Err(ContractError::NoSlotAvailableLeft {});
+    }
+    WHITELIST.save(deps.storage, info.sender.clone(), &(nb_slot - quantity))?;
+
+    let mut deposit = lottery.config.price.clone();
+    deposit.amount *= Uint128::from(quantity);
+    if !has_coins(info.funds.as_slice(), &deposit) {
+        return Err(ContractError::NotEnoughMoneyForNft {});
+    }
+
+    escrow(deps.storage, &deposit)?;
+    LOTTERY_ENTRIES.save(
+        deps.storage,
+        (lottery_id, info.sender),
+        &LotteryEntry {
+            quantity,
+            deposit,
+            won: 0,
+        },
+    )?;
+    lottery.nb_entrants += 1;
+    lottery.nb_tickets += quantity;
+    LOTTERIES.save(deps.storage, lottery_id, &lottery)?;
+
+    Ok(Response::new().add_attribute("method", "try_register_lottery"))
+}
+
+/// Draw the lottery winners
+///
+/// Each requested token is a ticket, tickets are listed by address in ascending order. While
+/// tokens are left and tickets remain, the ticket at index `lottery_random(seed, draw) % number
+/// of remaining tickets` wins, `draw` counting from 0, and is swap-removed from the list.
+/// If there are no more tickets than tokens, every ticket wins. The list is bounded by
+/// `MAX_LOTTERY_TICKETS`.
+pub fn try_draw_lottery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seed: String,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let lottery_id = LOTTERY_COUNT.load(deps.storage)?;
+    let mut lottery = load_lottery(&deps, lottery_id)?;
+    if lottery.seed.is_some() {
+        return Err(ContractError::LotteryAlreadyDrawn {});
+    }
+    if env.block.time.seconds() < lottery.config.registration_end {
+        return Err(ContractError::RegistrationNotEnded {});
+    }
+    if env.block.time.seconds() >= lottery.config.draw_deadline {
+        return Err(ContractError::LotteryDrawExpired {});
+    }
+    if hex::encode(Sha256::digest(seed.as_bytes())) != lottery.config.seed_hash {
+        return Err(ContractError::LotterySeedMismatch {});
+    }
+
+    let entries: Vec<(Addr, LotteryEntry)> = LOTTERY_ENTRIES
+        .prefix(lottery_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let mut tickets: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (_, entry))| std::iter::repeat_n(index, entry.quantity as usize))
+        .collect();
+
+    let mut won = vec![0u32; entries.len()];
+    if tickets.len() <= lottery.config.supply as usize {
+        for index in tickets {
+            won[index] += 1;
+        }
+    } else {
+        for draw in 0..lottery.config.supply {
+            let ticket = (lottery_random(&seed, draw) % tickets.len() as u64) as usize;
+            won[tickets.swap_remove(ticket)] += 1;
+        }
+    }
+
+    for ((address, mut entry), won) in entries.into_iter().zip(won) {
+        if won > 0 {
+            entry.won = won;
+            LOTTERY_ENTRIES.save(deps.storage, (lottery_id, address), &entry)?;
+            lottery.nb_winning_tickets += won;
+        }
+    }
+
+    close_lottery(deps.storage, &mut lottery)?;
+
+    lottery.seed = Some(seed);
+    let nb_winning_tickets = lottery.nb_winning_tickets;
+    LOTTERIES.save(deps.storage, lottery_id, &lottery)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "try_draw_lottery")
+        .add_attribute("nb_winning_tickets", nb_winning_tickets.to_string()))
+}
+
+pub fn try_claim_lottery(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lottery_id: u64,
+) -> Result<Response, ContractError> {
+    let mut lottery = load_lottery(&deps, lottery_id)?;
+    if lottery.seed.is_none() {
+        // Without a draw before the deadline, every ticket is refunded
+        if env.block.time.seconds() < lottery.config.draw_deadline {
+            return Err(ContractError::LotteryNotDrawn {});
+        }
+        close_lottery(deps.storage, &mut lottery)?;
+    }
+
+    let key = (lottery_id, info.sender.clone());
+    let entry = match LOTTERY_ENTRIES.may_load(deps.storage, key.clone())? {
+        Some(entry) => entry,
+        None => return Err(ContractError::LotteryEntryNotFound {}),
+    };
+    LOTTERY_ENTRIES.remove(deps.storage, key);
+    release(deps.storage, &entry.deposit)?;
+
+    lottery.nb_claimed += 1;
+    LOTTERIES.save(deps.storage, lottery_id, &lottery)?;
+
+    let mut payment = lottery.config.price.clone();
+    payment.amount *= Uint128::from(entry.won);
+
+    let mut response = Response::new().add_attribute("method", "try_claim_lottery");
+
+    if entry.won > 0 {
+        record_sale(
+            &mut deps,
+            &env,
+            &info.sender,
+            MintPhase::PreSale,
+            entry.won,
+            std::slice::from_ref(&payment),
+        )?;
+
+        let mut state = STATE.load(deps.storage)?;
+        for _ in 0..entry.won {
+            next_token_id(&deps, &mut state);
+            let metadata = token_metadata(
+                &deps,
+                &env,
+                state.total_market_supply + state.total_reserved_supply,
+                state.metadata.clone(),
+                state.last_token_id,
+                MintPhase::PreSale,
+            )?;
+            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+                msg: to_json_binary(&mint_helper(
+                    state.last_token_id,
+                    info.sender.to_string(),
+                    metadata,
+                ))?,
+                funds: vec![],
+            }));
+        }
+        STATE.save(deps.storage, &state)?;
+    }
+
+    // Refund the tickets not won and give their whitelist slots back
+    let refund = entry.deposit.amount - payment.amount;
+    if !refund.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: payment.denom,
+                amount: refund,
+            }],
+        });
+    }
+    WHITELIST.update(deps.storage, info.sender, |nb_slot| -> StdResult<_> {
+        Ok(nb_slot.unwrap_or(0) + entry.quantity - entry.won)
+    })?;
+
+    Ok(response.add_attribute("won", entry.won.to_string()))
+}
+
+fn load_lottery(deps: &DepsMut, lottery_id: u64) -> Result<Lottery, ContractError> {
+    match LOTTERIES.may_load(deps.storage, lottery_id)? {
+        Some(lottery) => Ok(lottery),
+        None => Err(ContractError::LotteryNotFound {}),
+    }
+}
+
+/// Give the tokens not won back to the market supply, once
+fn close_lottery(storage: &mut dyn Storage, lottery: &mut Lottery) -> Result<(), ContractError> {
+    if !lottery.closed {
+        let mut state = STATE.load(storage)?;
+        state.total_market_minted -= lottery.config.supply - lottery.nb_winning_tickets;
+        STATE.save(storage, &state)?;
+        lottery.closed = true;
+    }
+    Ok(())
+}
+
+/// Lottery random number
+///
+/// First 8 bytes (big endian) of sha256(seed | draw), the draw index being encoded big endian.
+/// The seed is committed before the registrations open, so the draw can't be predicted by the
+/// entrants and can be replayed by anybody once the seed is revealed.
+pub fn lottery_random(seed: &str, draw: u32) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.as_bytes());
+    hasher.update(draw.to_be_bytes());
+    let hash = hasher.finalize();
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[0..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Deposit a pre-minted token into the inventory, through the cw721 `SendNft` hook
+pub fn try_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // Only the tokens of the NFT contract, sent by an admin
+    if info.sender != NFT_CONTRACT.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    is_admin_or_owner(
+        &deps,
+        MessageInfo {
+            sender: deps.api.addr_validate(&msg.sender)?,
+            funds: vec![],
+        },
+    )?;
+
+    let deposit = INVENTORY_COUNT.load(deps.storage)? + 1;
+    INVENTORY_COUNT.save(deps.storage, &deposit)?;
+    INVENTORY.save(deps.storage, deposit, &msg.token_id)?;
+    INVENTORY_DEPOSITS.save(deps.storage, &msg.token_id, &deposit)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "try_receive_nft")
+        .add_attribute("token_id", msg.token_id))
+}
+
+pub fn update_inventory_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    mode: Option<InventoryOrder>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    INVENTORY_MODE.save(deps.storage, &mode)?;
+
+    Ok(Response::new().add_attribute("method", "update_inventory_mode"))
+}
+
+pub fn try_withdraw_inventory(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let recipient = match deps.api.addr_validate(&recipient) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address: recipient }),
+    };
+
+    let mut response = Response::new().add_attribute("method", "try_withdraw_inventory");
+    let nft_contract = NFT_CONTRACT.load(deps.storage)?;
+    for token_id in token_ids {
+        let deposit = match INVENTORY_DEPOSITS.may_load(deps.storage, &token_id)? {
+            Some(deposit) => deposit,
+            None => return Err(ContractError::InventoryTokenNotFound { token_id }),
+        };
+        INVENTORY.remove(deps.storage, deposit);
+        INVENTORY_DEPOSITS.remove(deps.storage, &token_id);
+        response = response.add_message(transfer_helper(&nft_contract, token_id, &recipient)?);
+    }
+
+    Ok(response)
+}
+
+/// Take a token out of the inventory, the oldest deposit or a pseudo-random one
+///
+/// The pseudo-random pick draws a deposit number between the oldest and the newest deposit,
+/// with `pseudo_random`, and takes the first token deposited from there. It is predictable and
+/// tokens following a gap in the deposit numbers are more likely to be picked, it is not fair.
+fn take_from_inventory(
+    deps: &mut DepsMut,
+    env: &Env,
+    sender: &Addr,
+    order: &InventoryOrder,
+    draw: u32,
+) -> Result<TokenID, ContractError> {
+    let first = match INVENTORY
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+    {
+        Some(first) => first?,
+        None => return Err(ContractError::NotEnoughNftLeft {}),
+    };
+
+    let deposit = match order {
+        InventoryOrder::Sequential => first,
+        InventoryOrder::Random => {
+            let last = INVENTORY_COUNT.load(deps.storage)?;
+            let start = first + pseudo_random(env, sender, draw) % (last - first + 1);
+            INVENTORY
+                .keys(
+                    deps.storage,
+                    Some(Bound::inclusive(start)),
+                    None,
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?
+                .unwrap_or(first)
+        }
+    };
+    let token_id = INVENTORY.load(deps.storage, deposit)?;
+    INVENTORY.remove(deps.storage, deposit);
+    INVENTORY_DEPOSITS.remove(deps.storage, &token_id);
+
+    Ok(token_id)
+}
+
+fn transfer_helper(
+    nft_contract: &Addr,
+    token_id: TokenID,
+    recipient: &Addr,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: to_json_binary(&cw_carbonable_lib::ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id,
+        })?,
+        funds: vec![],
+    }))
+}
+
+pub fn set_plots(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    plots: Vec<PlotEntry>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    // check validity of new admin address
-    let admin_to_remove = deps.api.addr_validate(address.as_str());
-    if admin_to_remove.is_err() {
-        return Err(ContractError::InvalidAddress { address });
+    // Plot prices replace the sell price, they are timelocked too
+    let response = schedule_change(deps, &env, ConfigChange::Plots(plots))?;
+
+    Ok(response.add_attribute("method", "set_plots"))
+}
+
+pub fn remove_plot(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: u32,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    match PLOTS.may_load(deps.storage, token_id)? {
+        Some(plot) if plot.sold => return Err(ContractError::PlotSold { token_id }),
+        Some(_) => {}
+        None => return Err(ContractError::PlotNotFound { token_id }),
     }
+    PLOTS.remove(deps.storage, token_id);
 
-    // check if new admin
-    if !ADMIN_WALLETS
-        .load(deps.storage)?
-        .contains(admin_to_remove.as_ref().unwrap())
-    {
-        return Err(ContractError::AddressNotFound { address });
+    // Give the plot back to the market supply
+    let mut state = STATE.load(deps.storage)?;
+    state.total_market_minted -= 1;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attribute("method", "remove_plot"))
+}
+
+pub fn try_buy_specific(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<u32>,
+    max_price: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let quantity = token_ids.len() as u32;
+
+    // Check quantity validity
+    is_multi_buy_quantity_too_big(quantity, &state)?;
+
+    // Is sell open ? Whitelist slots are burnt in pre-sale
+    let phase = is_sell_available(&mut deps, &info, quantity)?;
+
+    let mut response = Response::new();
+    let mut sell_price = vec![];
+    for token_id in token_ids {
+        let mut plot = match PLOTS.may_load(deps.storage, token_id)? {
+            Some(plot) if plot.sold => return Err(ContractError::PlotSold { token_id }),
+            Some(plot) => plot,
+            None => return Err(ContractError::PlotNotFound { token_id }),
+        };
+        plot.sold = true;
+        PLOTS.save(deps.storage, token_id, &plot)?;
+        add_coin(
+            &mut sell_price,
+            &convert_price(deps.as_ref(), &env, plot.price)?,
+        );
+
+        let metadata = token_metadata(
+            &deps,
+            &env,
+            state.total_market_supply + state.total_reserved_supply,
+            plot.metadata,
+            token_id,
+            phase,
+        )?;
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+            msg: to_json_binary(&mint_helper(token_id, info.sender.to_string(), metadata))?,
+            funds: vec![],
+        }));
     }
 
-    ADMIN_WALLETS.update(
+    // Does the buy has enough coins ?
+    for price in sell_price.iter() {
+        is_price_paid(&info, price, &max_price)?;
+    }
+    record_sale(&mut deps, &env, &info.sender, phase, quantity, &sell_price)?;
+
+    Ok(response.add_attribute("method", "try_buy_specific"))
+}
+
+/// Check the price is accepted by the buyer and paid, a fully discounted purchase needs no funds
+fn is_price_paid(
+    info: &MessageInfo,
+    price: &Coin,
+    max_price: &Option<Coin>,
+) -> Result<(), ContractError> {
+    if let Some(max_price) = max_price {
+        if price.denom != max_price.denom || price.amount > max_price.amount {
+            return Err(ContractError::PriceAboveMax {
+                price: price.clone(),
+            });
+        }
+    }
+    if !price.amount.is_zero() && !has_coins(info.funds.as_slice(), price) {
+        return Err(ContractError::NotEnoughMoneyForNft {});
+    }
+
+    Ok(())
+}
+
+/// Bump `last_token_id`, skipping the ids registered as plots
+fn next_token_id(deps: &DepsMut, state: &mut State) {
+    state.last_token_id += 1;
+    while PLOTS.has(deps.storage, state.last_token_id) {
+        state.last_token_id += 1;
+    }
+}
+
+pub fn try_fulfill_fiat_order(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: String,
+    recipient: String,
+    quantity: u32,
+) -> Result<Response, ContractError> {
+    // Is fulfiller wallet ?
+    if !FULFILLER_WALLETS.load(deps.storage)?.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // An order is delivered once
+    if FIAT_ORDERS.has(deps.storage, &order_id) {
+        return Err(ContractError::FiatOrderAlreadyFulfilled { order_id });
+    }
+
+    let recipient = match deps.api.addr_validate(&recipient) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address: recipient }),
+    };
+
+    // Check quantity validity
+    if quantity == 0 {
+        return Err(ContractError::InvalidQuantity {});
+    }
+    let mut state = STATE.load(deps.storage)?;
+    is_multi_buy_quantity_too_big(quantity, &state)?;
+
+    // Inventory mode, the order is delivered with pre-minted tokens
+    let inventory = INVENTORY_MODE.load(deps.storage)?;
+    if inventory.is_none() {
+        is_waitlist_served(&deps, &state)?;
+        state.total_market_minted += quantity;
+        // Is some NFT available ?
+        is_market_nft_available(&state)?;
+    }
+
+    // Paid off-chain, no revenue
+    record_sale(
+        &mut deps,
+        &env,
+        &recipient,
+        MintPhase::PublicSale,
+        quantity,
+        &[],
+    )?;
+
+    let mut response = Response::new();
+    let mut token_ids = vec![];
+    for draw in 0..quantity {
+        if let Some(order) = &inventory {
+            let token_id = take_from_inventory(&mut deps, &env, &recipient, order, draw)?;
+            response = response.add_message(transfer_helper(
+                &NFT_CONTRACT.load(deps.storage)?,
+                token_id.clone(),
+                &recipient,
+            )?);
+            token_ids.push(token_id);
+            continue;
+        }
+
+        next_token_id(&deps, &mut state);
+        token_ids.push(state.last_token_id.to_string());
+
+        let base = pick_metadata_template(&mut deps, &env, &recipient, state.last_token_id)?
+            .unwrap_or_else(|| state.metadata.clone());
+        let metadata = token_metadata(
+            &deps,
+            &env,
+            state.total_market_supply + state.total_reserved_supply,
+            base,
+            state.last_token_id,
+            MintPhase::PublicSale,
+        )?;
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+            msg: to_json_binary(&mint_helper(
+                state.last_token_id,
+                recipient.to_string(),
+                metadata,
+            ))?,
+            funds: vec![],
+        }));
+    }
+    STATE.save(deps.storage, &state)?;
+
+    FIAT_ORDERS.save(
         deps.storage,
-        |mut wallets| -> Result<HashSet<Addr>, ContractError> {
-            wallets.remove(&admin_to_remove.unwrap());
-            Ok(wallets)
+        &order_id,
+        &FiatOrder {
+            recipient,
+            quantity,
+            token_ids,
+            fulfiller: info.sender,
+            fulfilled: env.block.time.seconds(),
         },
     )?;
 
-    let response = Response::new();
-    Ok(response.add_attribute("method", "remove_admin"))
+    Ok(response
+        .add_attribute("method", "try_fulfill_fiat_order")
+        .add_attribute("order_id", order_id))
 }
 
-pub fn add_admin(
+pub fn add_fulfiller(
     deps: DepsMut,
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
-    // Security : owner has all rights
+    // Is admin or owner wallet ?
     is_admin_or_owner(&deps, info)?;
 
-    // check validity of new admin address
-    let admin_to_add = deps.api.addr_validate(address.as_str());
-    if admin_to_add.is_err() {
-        return Err(ContractError::InvalidAddress { address });
-    }
+    let wallet = match deps.api.addr_validate(&address) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address }),
+    };
 
-    // check if new admin
-    if ADMIN_WALLETS
-        .load(deps.storage)?
-        .contains(admin_to_add.as_ref().unwrap())
-    {
+    let mut wallets = FULFILLER_WALLETS.load(deps.storage)?;
+    if !wallets.insert(wallet) {
         return Err(ContractError::AddressAlreadyRegistered { address });
     }
+    FULFILLER_WALLETS.save(deps.storage, &wallets)?;
 
-    ADMIN_WALLETS.update(
+    Ok(Response::new().add_attribute("method", "add_fulfiller"))
+}
+
+pub fn remove_fulfiller(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let wallet = match deps.api.addr_validate(&address) {
+        Ok(addr) => addr,
+        Err(_) => return Err(ContractError::InvalidAddress { address }),
+    };
+
+    let mut wallets = FULFILLER_WALLETS.load(deps.storage)?;
+    if !wallets.remove(&wallet) {
+        return Err(ContractError::AddressNotFound { address });
+    }
+    FULFILLER_WALLETS.save(deps.storage, &wallets)?;
+
+    Ok(Response::new().add_attribute("method", "remove_fulfiller"))
+}
+
+pub fn try_join_waitlist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    quantity: u32,
+    max_price: Option<Coin>,
+) -> Result<Response, ContractError> {
+    // The waitlist is for the public sale
+    if sell_phase(&deps)? != MintPhase::PublicSale {
+        return Err(ContractError::SellClose {});
+    }
+
+    let state = STATE.load(deps.storage)?;
+    is_multi_buy_quantity_too_big(quantity, &state)?;
+    if state.total_market_minted + quantity <= state.total_market_supply {
+        return Err(ContractError::SupplyAvailable {});
+    }
+
+    // The deposit is locked at the current oracle rate
+    let mut deposit = convert_price(deps.as_ref(), &env, state.sell_price)?;
+    deposit.amount *= Uint128::from(quantity);
+    is_price_paid(&info, &deposit, &max_price)?;
+
+    escrow(deps.storage, &deposit)?;
+    let entry_id = WAITLIST_COUNT.load(deps.storage)? + 1;
+    WAITLIST_COUNT.save(deps.storage, &entry_id)?;
+    WAITLIST.save(
         deps.storage,
-        |mut wallets| -> Result<HashSet<Addr>, ContractError> {
-            wallets.insert(admin_to_add.unwrap());
-            Ok(wallets)
+        entry_id,
+        &WaitlistEntry {
+            buyer: info.sender,
+            quantity,
+            deposit,
+            joined: env.block.time.seconds(),
         },
     )?;
 
-    let response = Response::new();
-    Ok(response.add_attribute("method", "add_admin"))
+    Ok(Response::new()
+        .add_attribute("method", "try_join_waitlist")
+        .add_attribute("entry_id", entry_id.to_string()))
 }
 
-fn mint_helper(nft_id: u32, addr: String, state: State) -> cw_carbonable_lib::ExecuteMsg {
-    let root_token_uri = state.metadata.external_url.clone().unwrap_or("".to_string());
-    let token_uri = format!("{}{}", root_token_uri, nft_id.to_string());
-    cw_carbonable_lib::ExecuteMsg::Mint(cw721_base::MintMsg::<Extension> {
-        token_id: nft_id.to_string(),
-        owner: addr,
-        token_uri: Some(token_uri),
-        extension: Extension::from(state.metadata),
-    })
+pub fn try_leave_waitlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    entry_id: u64,
+) -> Result<Response, ContractError> {
+    let entry = match WAITLIST.may_load(deps.storage, entry_id)? {
+        Some(entry) if entry.buyer == info.sender => entry,
+        _ => return Err(ContractError::WaitlistEntryNotFound { entry_id }),
+    };
+    WAITLIST.remove(deps.storage, entry_id);
+    release(deps.storage, &entry.deposit)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: entry.buyer.to_string(),
+            amount: vec![entry.deposit],
+        })
+        .add_attribute("method", "try_leave_waitlist"))
 }
 
-pub fn is_market_nft_available(state: &State) -> Result<(), ContractError> {
-    if state.total_market_minted > state.total_market_supply {
-        return Err(ContractError::NotEnoughNftLeft {});
+/// Maximum waitlist entries fulfilled by a call
+const WAITLIST_FULFILL_LIMIT: u32 = 10;
+
+pub fn try_fulfill_waitlist(
+    mut deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit
+        .unwrap_or(WAITLIST_FULFILL_LIMIT)
+        .min(WAITLIST_FULFILL_LIMIT);
+    let response = fulfill_waitlist(&mut deps, &env, limit)?;
+
+    Ok(response.add_attribute("method", "try_fulfill_waitlist"))
+}
+
+/// Mint up to `limit` waitlist entries, in FIFO order, while the market supply allows it
+///
+/// Stops at the first entry that doesn't fit, so later entries can't jump the queue.
+fn fulfill_waitlist(deps: &mut DepsMut, env: &Env, limit: u32) -> Result<Response, ContractError> {
+    let entries: Vec<(u64, WaitlistEntry)> = WAITLIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<_>>()?;
+
+    let mut state = STATE.load(deps.storage)?;
+    let mut response = Response::new();
+    for (entry_id, entry) in entries {
+        if state.total_market_minted + entry.quantity > state.total_market_supply {
+            break;
+        }
+        state.total_market_minted += entry.quantity;
+        WAITLIST.remove(deps.storage, entry_id);
+        release(deps.storage, &entry.deposit)?;
+
+        record_sale(
+            deps,
+            env,
+            &entry.buyer,
+            MintPhase::PublicSale,
+            entry.quantity,
+            std::slice::from_ref(&entry.deposit),
+        )?;
+
+        for _ in 0..entry.quantity {
+            next_token_id(deps, &mut state);
+
+            let base = pick_metadata_template(deps, env, &entry.buyer, state.last_token_id)?
+                .unwrap_or_else(|| state.metadata.clone());
+            let metadata = token_metadata(
+                deps,
+                env,
+                state.total_market_supply + state.total_reserved_supply,
+                base,
+                state.last_token_id,
+                MintPhase::PublicSale,
+            )?;
+            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT.load(deps.storage)?.to_string(),
+                msg: to_json_binary(&mint_helper(
+                    state.last_token_id,
+                    entry.buyer.to_string(),
+                    metadata,
+                ))?,
+                funds: vec![],
+            }));
+        }
+        response = response.add_attribute("waitlist_fulfilled", entry_id.to_string());
     }
+    STATE.save(deps.storage, &state)?;
 
-    Ok(())
+    Ok(response)
 }
 
-pub fn is_reserved_nft_available(state: &State) -> Result<(), ContractError> {
-    if state.total_reserved_minted > state.total_reserved_supply {
-        return Err(ContractError::NotEnoughNftLeft {});
+pub fn update_gating_contracts(
+    deps: DepsMut,
+    info: MessageInfo,
+    contracts: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // Check validity of contract addresses
+    let contracts = contracts
+        .into_iter()
+        .map(|contract| match deps.api.addr_validate(&contract) {
+            Ok(addr) => Ok(addr),
+            Err(_) => Err(ContractError::InvalidAddress { address: contract }),
+        })
+        .collect::<Result<Vec<Addr>, _>>()?;
+    GATING_CONTRACTS.save(deps.storage, &contracts)?;
+
+    Ok(Response::new().add_attribute("method", "update_gating_contracts"))
+}
+
+/// Page size of the cw721 `Tokens` queries
+const TOKENS_PAGE_SIZE: u32 = 30;
+
+/// Use `nb_to_buy` gating tokens held by the buyer, one pre-sale slot per token
+///
+/// Tokens are taken in the order of the gating contracts, then of their token ids.
+fn use_gating_tokens(
+    deps: &mut DepsMut,
+    buyer: &Addr,
+    nb_to_buy: u32,
+) -> Result<(), ContractError> {
+    let mut nb_held = 0;
+    let mut usable: Vec<(Addr, String)> = vec![];
+
+    'contracts: for contract in GATING_CONTRACTS.load(deps.storage)? {
+        let mut start_after = None;
+        loop {
+            let res: TokensResponse = deps.querier.query_wasm_smart(
+                contract.to_string(),
+                &Cw721QueryMsg::Tokens {
+                    owner: buyer.to_string(),
+                    start_after,
+                    limit: Some(TOKENS_PAGE_SIZE),
+                },
+            )?;
+            nb_held += res.tokens.len();
+
+            for token_id in res.tokens.iter() {
+                if usable.len() == nb_to_buy as usize {
+                    break 'contracts;
+                }
+                if !USED_GATING_TOKENS.has(deps.storage, (contract.clone(), token_id.clone())) {
+                    usable.push((contract.clone(), token_id.clone()));
+                }
+            }
+
+            if res.tokens.len() < TOKENS_PAGE_SIZE as usize {
+                break;
+            }
+            start_after = res.tokens.last().cloned();
+        }
+    }
+
+    if usable.len() < nb_to_buy as usize {
+        if nb_held == 0 {
+            return Err(ContractError::AddressNotWhitelisted {});
+        }
+        return Err(ContractError::NoSlotAvailableLeft {});
+    }
+
+    // burn gating tokens
+    for key in usable {
+        USED_GATING_TOKENS.save(deps.storage, key, buyer)?;
     }
 
     Ok(())
 }
 
-pub fn is_multi_buy_quantity_too_big(quantity: u32, state: &State) -> Result<(), ContractError> {
-    if quantity > state.max_buy_at_once {
-        return Err(ContractError::MultiBuyQuantityTooHigh {});
+pub fn update_loyalty(
+    deps: DepsMut,
+    info: MessageInfo,
+    contracts: Vec<String>,
+    tiers: Vec<LoyaltyTier>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    // Check validity of contract addresses
+    let contracts = contracts
+        .into_iter()
+        .map(|contract| match deps.api.addr_validate(&contract) {
+            Ok(addr) => Ok(addr),
+            Err(_) => Err(ContractError::InvalidAddress { address: contract }),
+        })
+        .collect::<Result<Vec<Addr>, _>>()?;
+
+    if tiers
+        .iter()
+        .any(|tier| tier.min_tokens == 0 || tier.discount > Decimal::one())
+    {
+        return Err(ContractError::InvalidLoyaltyTier {});
     }
 
-    Ok(())
+    LOYALTY.save(deps.storage, &LoyaltyConfig { contracts, tiers })?;
+
+    Ok(Response::new().add_attribute("method", "update_loyalty"))
+}
+
+/// Tokens held by an address on the loyalty contracts and the discount of the best tier reached
+///
+/// Tokens used by another buyer don't count. Tokens are only counted until the highest tier is
+/// reached, to bound the number of queries.
+pub fn loyalty_discount(deps: Deps, address: &Addr) -> StdResult<(Vec<(Addr, String)>, Decimal)> {
+    let loyalty = LOYALTY.load(deps.storage)?;
+    let max_tokens = match loyalty.tiers.iter().map(|tier| tier.min_tokens).max() {
+        Some(max_tokens) => max_tokens as usize,
+        None => return Ok((vec![], Decimal::zero())),
+    };
+
+    let mut tokens: Vec<(Addr, String)> = vec![];
+    'contracts: for contract in loyalty.contracts {
+        let mut start_after = None;
+        loop {
+            let res: TokensResponse = deps.querier.query_wasm_smart(
+                contract.to_string(),
+                &Cw721QueryMsg::Tokens {
+                    owner: address.to_string(),
+                    start_after,
+                    limit: Some(TOKENS_PAGE_SIZE),
+                },
+            )?;
+            for token_id in res.tokens.iter() {
+                if tokens.len() == max_tokens {
+                    break 'contracts;
+                }
+                let key = (contract.clone(), token_id.clone());
+                match USED_LOYALTY_TOKENS.may_load(deps.storage, key.clone())? {
+                    Some(buyer) if buyer != *address => {}
+                    _ => tokens.push(key),
+                }
+            }
+
+            if res.tokens.len() < TOKENS_PAGE_SIZE as usize {
+                break;
+            }
+            start_after = res.tokens.last().cloned();
+        }
+    }
+
+    let discount = loyalty
+        .tiers
+        .iter()
+        .filter(|tier| tier.min_tokens as usize <= tokens.len())
+        .max_by_key(|tier| tier.min_tokens)
+        .map(|tier| tier.discount)
+        .unwrap_or_default();
+
+    Ok((tokens, discount))
+}
+
+pub fn update_oracle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    oracle: Option<OracleConfig>,
+) -> Result<Response, ContractError> {
+    // Is admin or owner wallet ?
+    is_admin_or_owner(&deps, info)?;
+
+    let oracle = match oracle {
+        Some(config) => {
+            let contract = match deps.api.addr_validate(&config.contract) {
+                Ok(addr) => addr,
+                Err(_) => {
+                    return Err(ContractError::InvalidAddress {
+                        address: config.contract,
+                    })
+                }
+            };
+            let check_contract = match deps.api.addr_validate(&config.check_contract) {
+                Ok(addr) => addr,
+                Err(_) => {
+                    return Err(ContractError::InvalidAddress {
+                        address: config.check_contract,
+                    })
+                }
+            };
+            Some(Oracle {
+                contract,
+                check_contract,
+                reference_denom: config.reference_denom,
+                payment_denom: config.payment_denom,
+                max_staleness: config.max_staleness,
+                max_deviation: config.max_deviation,
+            })
+        }
+        None => None,
+    };
+
+    // The oracle reprices the sale, it is timelocked like the sell price
+    let response = schedule_change(deps, &env, ConfigChange::Oracle(oracle))?;
+
+    Ok(response.add_attribute("method", "update_oracle"))
+}
+
+/// Convert a price in the oracle reference currency to the payment denom
+///
+/// The rate must be fresh on both price sources and they must agree within `max_deviation`.
+/// Prices in any other denom are returned as is.
+pub fn convert_price(deps: Deps, env: &Env, price: Coin) -> Result<Coin, ContractError> {
+    let oracle = match ORACLE.load(deps.storage)? {
+        Some(oracle) if oracle.reference_denom == price.denom => oracle,
+        _ => return Ok(price),
+    };
+
+    let rate = oracle_rate(deps, env, &oracle, &oracle.contract)?;
+    let check_rate = oracle_rate(deps, env, &oracle, &oracle.check_contract)?;
+    let deviation = if rate > check_rate {
+        rate - check_rate
+    } else {
+        check_rate - rate
+    };
+    if deviation > check_rate * oracle.max_deviation {
+        return Err(ContractError::OraclePriceDeviation {});
+    }
+
+    Ok(Coin {
+        denom: oracle.payment_denom,
+        amount: price.amount * rate,
+    })
+}
+
+fn oracle_rate(
+    deps: Deps,
+    env: &Env,
+    oracle: &Oracle,
+    contract: &Addr,
+) -> Result<Decimal, ContractError> {
+    let res: OraclePriceResponse = deps.querier.query_wasm_smart(
+        contract.to_string(),
+        &OracleQueryMsg::Price {
+            base: oracle.reference_denom.clone(),
+            quote: oracle.payment_denom.clone(),
+        },
+    )?;
+    if env.block.time.seconds() > res.updated_at + oracle.max_staleness {
+        return Err(ContractError::OraclePriceStale {});
+    }
+
+    Ok(res.rate)
 }
 
 pub fn is_sell_available(
     deps: &mut DepsMut,
     info: &MessageInfo,
     nb_to_buy: u32,
-) -> Result<(), ContractError> {
-    let is_sell_open = SELL_MODE.load(deps.storage)?;
-    let is_pre_sell_open = PRE_SELL_MODE.load(deps.storage)?;
-
+) -> Result<MintPhase, ContractError> {
     // Is sell open ?
-    if !is_sell_open && !is_pre_sell_open {
-        return Err(ContractError::SellClose {});
-    }
+    let phase = sell_phase(deps)?;
 
     // Check for whitelist
-    if !is_sell_open {
+    if phase == MintPhase::PreSale {
         // Check if send is in whitelist ?
         if !WHITELIST.has(deps.storage, info.sender.clone()) {
+            // Holders of the gating collections don't need a whitelist entry
+            if !GATING_CONTRACTS.load(deps.storage)?.is_empty() {
+                use_gating_tokens(deps, &info.sender, nb_to_buy)?;
+                return Ok(phase);
+            }
             return Err(ContractError::AddressNotWhitelisted {});
         }
 
@@ -442,7 +3990,7 @@ pub fn is_sell_available(
         WHITELIST.save(deps.storage, info.sender.clone(), &nb_slot)?;
     }
 
-    Ok(())
+    Ok(phase)
 }
 
 pub fn is_admin_or_owner(deps: &DepsMut, info: MessageInfo) -> Result<(), ContractError> {
@@ -456,6 +4004,24 @@ pub fn is_admin_or_owner(deps: &DepsMut, info: MessageInfo) -> Result<(), Contra
     Ok(())
 }
 
+pub fn is_owner(deps: &DepsMut, info: &MessageInfo) -> Result<(), ContractError> {
+    if OWNER_WALLET.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+/// Check a hex encoded sha256 hash
+pub fn is_hash_valid(hash: &str) -> Result<(), ContractError> {
+    match hex::decode(hash) {
+        Ok(bytes) if bytes.len() == 32 => Ok(()),
+        _ => Err(ContractError::InvalidHash {
+            hash: hash.to_string(),
+        }),
+    }
+}
+
 pub fn is_addresses_valid(deps: &DepsMut, receiver: &[String]) -> Result<(), ContractError> {
     // Check validity of drop receivers addresses
     for client in receiver {
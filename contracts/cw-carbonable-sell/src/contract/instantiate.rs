@@ -1,10 +1,15 @@
 use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::InitMsg;
 use crate::state::{
-    State, ADMIN_WALLETS, NFT_CONTRACT, OWNER_WALLET, PRE_SELL_MODE, SELL_MODE, STATE,
+    LoyaltyConfig, SaleStats, State, WithdrawConfig, ADMIN_WALLETS, AUCTION_COUNT,
+    AUTO_RELEASE_RESERVED, CURRENT_ROUND, FULFILLER_WALLETS, GATING_CONTRACTS,
+    GENERATED_ATTRIBUTES, INVENTORY_COUNT, INVENTORY_MODE, LOTTERY_COUNT, LOYALTY, NFT_CONTRACT,
+    ORACLE, OWNER_WALLET, PENDING_CHANGE_COUNT, PRE_SELL_MODE, REFERRAL_SHARE, SALE_STATS,
+    SELL_MODE, STATE, TIMELOCK_DELAY, TREASURY_WALLETS, WAITLIST_COUNT, WITHDRAW_CONFIG,
+    WITHDRAW_PROPOSAL_COUNT,
 };
 use crate::ContractError;
-use cosmwasm_std::{coin, Addr, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{coin, Addr, Decimal, DepsMut, MessageInfo, Response};
 use cw2::set_contract_version;
 use cw_carbonable_lib::Metadata;
 use std::collections::HashSet;
@@ -23,10 +28,63 @@ pub fn _instantiate(
     // empty list of admins
     ADMIN_WALLETS.save(deps.storage, &HashSet::new())?;
 
+    // pre-sale access only through the whitelist
+    GATING_CONTRACTS.save(deps.storage, &vec![])?;
+
+    // no holder discount
+    LOYALTY.save(deps.storage, &LoyaltyConfig::default())?;
+
+    // prices in the payment denom
+    ORACLE.save(deps.storage, &None)?;
+
+    // no fiat orders until a fulfiller is added
+    FULFILLER_WALLETS.save(deps.storage, &HashSet::new())?;
+
     // set sales mode
     SELL_MODE.save(deps.storage, &msg.sell_mode)?;
     PRE_SELL_MODE.save(deps.storage, &msg.pre_sell_mode)?;
 
+    // reserved supply stays reserved until released explicitly
+    AUTO_RELEASE_RESERVED.save(deps.storage, &false)?;
+
+    // tokens are minted on demand until the inventory mode is set
+    INVENTORY_MODE.save(deps.storage, &None)?;
+    INVENTORY_COUNT.save(deps.storage, &0)?;
+
+    // no sale round, the sell modes apply
+    CURRENT_ROUND.save(deps.storage, &None)?;
+
+    // empty waitlist
+    WAITLIST_COUNT.save(deps.storage, &0)?;
+
+    // no auction nor lottery yet
+    AUCTION_COUNT.save(deps.storage, &0)?;
+    LOTTERY_COUNT.save(deps.storage, &0)?;
+
+    // no generated attributes, every token gets the same metadata
+    GENERATED_ATTRIBUTES.save(deps.storage, &vec![])?;
+
+    // referrers earn nothing until a share is set
+    REFERRAL_SHARE.save(deps.storage, &Decimal::zero())?;
+
+    // funds can only be withdrawn to the owner until other wallets are added
+    TREASURY_WALLETS.save(deps.storage, &HashSet::from([info.sender.clone()]))?;
+    SALE_STATS.save(deps.storage, &SaleStats::default())?;
+
+    // a single approval sends the funds until the owner raises the threshold
+    WITHDRAW_CONFIG.save(
+        deps.storage,
+        &WithdrawConfig {
+            threshold: 1,
+            proposal_duration: 7 * 24 * 3600,
+        },
+    )?;
+    WITHDRAW_PROPOSAL_COUNT.save(deps.storage, &0)?;
+
+    // configuration changes apply right away until a delay is set
+    TIMELOCK_DELAY.save(deps.storage, &0)?;
+    PENDING_CHANGE_COUNT.save(deps.storage, &0)?;
+
     let state = State {
         total_market_supply: 0,
         total_reserved_supply: 0,
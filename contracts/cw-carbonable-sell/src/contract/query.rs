@@ -1,10 +1,78 @@
-use crate::msg::QueryMsg;
-use crate::state::{State, STATE};
-use cosmwasm_std::{to_binary, Binary, Deps, StdResult};
+use crate::contract::execute::{convert_price, loyalty_discount};
+use crate::msg::{
+    AuctionResponse, CollectionResponse, InventoryResponse, LotteryResponse,
+    MetadataTemplateResponse, PendingChangeResponse, PendingChangesResponse, PlotResponse,
+    PriceForResponse, ProductResponse, QueryMsg, RoundResponse, SaleStatsResponse,
+    SealedAuctionResponse, SealedBidResponse, TreasuryResponse, VoucherResponse,
+    WaitlistEntryResponse, WithdrawProposalResponse,
+};
+use crate::state::{
+    Bid, Collection, FiatOrder, GeneratedAttribute, LotteryEntry, LoyaltyConfig, Provenance,
+    ReferralStats, RoundConfig, State, WithdrawConfig, AUCTIONS, AUCTION_BIDS, COLLECTIONS,
+    CURRENT_ROUND, FIAT_ORDERS, FULFILLER_WALLETS, GATING_CONTRACTS, GENERATED_ATTRIBUTES,
+    INVENTORY, INVENTORY_DEPOSITS, INVENTORY_MODE, LOTTERIES, LOTTERY_COUNT, LOTTERY_ENTRIES,
+    LOYALTY, METADATA_TEMPLATES, ORACLE, PENDING_CHANGES, PLOTS, PRODUCTS, PROVENANCE, REFERRALS,
+    ROUNDS, ROUND_STATS, SALE_STATS, SEALED_AUCTIONS, SEALED_BIDS, STATE, TIMELOCK_DELAY,
+    TREASURY_WALLETS, USED_GATING_TOKENS, USED_LOYALTY_TOKENS, VOUCHERS, WAITLIST, WITHDRAW_CONFIG,
+    WITHDRAW_PROPOSALS,
+};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, Env, Order, StdError, StdResult, Uint128,
+};
+use cw_storage_plus::Bound;
 
-pub fn _query(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
+pub fn _query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::DumpState {} => to_binary(&dump_state(deps)?),
+        QueryMsg::DumpState {} => to_json_binary(&dump_state(deps)?),
+        QueryMsg::GeneratedAttributes {} => to_json_binary(&generated_attributes(deps)?),
+        QueryMsg::MetadataTemplates {} => to_json_binary(&metadata_templates(deps)?),
+        QueryMsg::Provenance {} => to_json_binary(&provenance(deps)?),
+        QueryMsg::ReferralStats { address } => to_json_binary(&referral_stats(deps, address)?),
+        QueryMsg::Voucher { code_hash } => to_json_binary(&voucher(deps, env, code_hash)?),
+        QueryMsg::Treasury {} => to_json_binary(&treasury(deps, env)?),
+        QueryMsg::WithdrawConfig {} => to_json_binary(&withdraw_config(deps)?),
+        QueryMsg::WithdrawProposals {} => to_json_binary(&withdraw_proposals(deps, env)?),
+        QueryMsg::PendingChanges {} => to_json_binary(&pending_changes(deps)?),
+        QueryMsg::SaleStats {} => to_json_binary(&sale_stats(deps)?),
+        QueryMsg::Round { round_id } => to_json_binary(&round(deps, round_id)?),
+        QueryMsg::Rounds {} => to_json_binary(&rounds(deps)?),
+        QueryMsg::Products {} => to_json_binary(&products(deps)?),
+        QueryMsg::Collection { collection_id } => to_json_binary(&collection(deps, collection_id)?),
+        QueryMsg::Collections {} => to_json_binary(&collections(deps)?),
+        QueryMsg::Auctions {} => to_json_binary(&auctions(deps)?),
+        QueryMsg::AuctionBids {
+            auction_id,
+            start_after,
+            limit,
+        } => to_json_binary(&auction_bids(deps, auction_id, start_after, limit)?),
+        QueryMsg::SealedAuctions {} => to_json_binary(&sealed_auctions(deps)?),
+        QueryMsg::SealedBids { auction_id } => to_json_binary(&sealed_bids(deps, auction_id)?),
+        QueryMsg::Lottery {} => to_json_binary(&lottery(deps)?),
+        QueryMsg::LotteryEntry {
+            lottery_id,
+            address,
+        } => to_json_binary(&lottery_entry(deps, lottery_id, address)?),
+        QueryMsg::Inventory { start_after, limit } => {
+            to_json_binary(&inventory(deps, start_after, limit)?)
+        }
+        QueryMsg::Plots { start_after, limit } => to_json_binary(&plots(deps, start_after, limit)?),
+        QueryMsg::GatingContracts {} => to_json_binary(&gating_contracts(deps)?),
+        QueryMsg::GatingTokenUsage { contract, token_id } => {
+            to_json_binary(&gating_token_usage(deps, contract, token_id)?)
+        }
+        QueryMsg::Loyalty {} => to_json_binary(&loyalty(deps)?),
+        QueryMsg::LoyaltyTokenUsage { contract, token_id } => {
+            to_json_binary(&loyalty_token_usage(deps, contract, token_id)?)
+        }
+        QueryMsg::Oracle {} => to_json_binary(&ORACLE.load(deps.storage)?),
+        QueryMsg::PriceFor { address, quantity } => {
+            to_json_binary(&price_for(deps, env, address, quantity)?)
+        }
+        QueryMsg::Waitlist { start_after, limit } => {
+            to_json_binary(&waitlist(deps, start_after, limit)?)
+        }
+        QueryMsg::FiatOrder { order_id } => to_json_binary(&fiat_order(deps, order_id)?),
+        QueryMsg::Fulfillers {} => to_json_binary(&fulfillers(deps)?),
     }
 }
 
@@ -13,3 +81,413 @@ pub fn dump_state(deps: Deps) -> StdResult<State> {
 
     Ok(state)
 }
+
+pub fn generated_attributes(deps: Deps) -> StdResult<Vec<GeneratedAttribute>> {
+    GENERATED_ATTRIBUTES.load(deps.storage)
+}
+
+pub fn metadata_templates(deps: Deps) -> StdResult<Vec<MetadataTemplateResponse>> {
+    METADATA_TEMPLATES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (template_id, template) = item?;
+            Ok(MetadataTemplateResponse {
+                template_id,
+                metadata: template.metadata,
+                weight: template.weight,
+                remaining: template.remaining,
+            })
+        })
+        .collect()
+}
+
+pub fn provenance(deps: Deps) -> StdResult<Option<Provenance>> {
+    PROVENANCE.may_load(deps.storage)
+}
+
+pub fn referral_stats(deps: Deps, address: String) -> StdResult<ReferralStats> {
+    let referrer = deps.api.addr_validate(&address)?;
+
+    Ok(REFERRALS
+        .may_load(deps.storage, referrer)?
+        .unwrap_or_default())
+}
+
+pub fn voucher(deps: Deps, env: Env, code_hash: String) -> StdResult<VoucherResponse> {
+    let voucher = VOUCHERS.load(deps.storage, &code_hash.to_lowercase())?;
+
+    let is_expired = match voucher.expires {
+        Some(expires) => env.block.time.seconds() > expires,
+        None => false,
+    };
+    let active = !is_expired && voucher.redemptions < voucher.max_redemptions;
+
+    Ok(VoucherResponse { voucher, active })
+}
+
+pub fn treasury(deps: Deps, env: Env) -> StdResult<TreasuryResponse> {
+    let mut wallets: Vec<Addr> = TREASURY_WALLETS.load(deps.storage)?.into_iter().collect();
+    wallets.sort();
+
+    Ok(TreasuryResponse {
+        balances: deps.querier.query_all_balances(env.contract.address)?,
+        revenue: SALE_STATS.load(deps.storage)?.revenue,
+        wallets,
+    })
+}
+
+pub fn withdraw_config(deps: Deps) -> StdResult<WithdrawConfig> {
+    WITHDRAW_CONFIG.load(deps.storage)
+}
+
+pub fn withdraw_proposals(deps: Deps, env: Env) -> StdResult<Vec<WithdrawProposalResponse>> {
+    WITHDRAW_PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, proposal)) => env.block.time.seconds() <= proposal.expires,
+            Err(_) => true,
+        })
+        .map(|item| {
+            let (proposal_id, proposal) = item?;
+            Ok(WithdrawProposalResponse {
+                proposal_id,
+                proposal,
+            })
+        })
+        .collect()
+}
+
+pub fn pending_changes(deps: Deps) -> StdResult<PendingChangesResponse> {
+    let changes = PENDING_CHANGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (change_id, pending) = item?;
+            Ok(PendingChangeResponse {
+                change_id,
+                change: pending.change,
+                activation: pending.activation,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PendingChangesResponse {
+        delay: TIMELOCK_DELAY.load(deps.storage)?,
+        changes,
+    })
+}
+
+pub fn sale_stats(deps: Deps) -> StdResult<SaleStatsResponse> {
+    let stats = SALE_STATS.load(deps.storage)?;
+
+    let tokens_sold = stats.pre_sale_tokens + stats.public_sale_tokens;
+    let average_tokens_per_buyer = match stats.nb_buyers {
+        0 => Decimal::zero(),
+        nb_buyers => Decimal::from_ratio(tokens_sold, nb_buyers),
+    };
+
+    Ok(SaleStatsResponse {
+        revenue: stats.revenue,
+        nb_buyers: stats.nb_buyers,
+        pre_sale_tokens: stats.pre_sale_tokens,
+        public_sale_tokens: stats.public_sale_tokens,
+        airdrop_tokens: stats.airdrop_tokens,
+        average_tokens_per_buyer,
+        first_sale: stats.first_sale,
+        last_sale: stats.last_sale,
+    })
+}
+
+pub fn round(deps: Deps, round_id: u32) -> StdResult<RoundResponse> {
+    let config = ROUNDS.load(deps.storage, round_id)?;
+
+    round_response(deps, round_id, config)
+}
+
+pub fn rounds(deps: Deps) -> StdResult<Vec<RoundResponse>> {
+    ROUNDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (round_id, config) = item?;
+            round_response(deps, round_id, config)
+        })
+        .collect()
+}
+
+fn round_response(deps: Deps, round_id: u32, config: RoundConfig) -> StdResult<RoundResponse> {
+    let stats = ROUND_STATS.load(deps.storage, round_id)?;
+    let open = match CURRENT_ROUND.load(deps.storage)? {
+        Some(current) => current.open && current.round_id == round_id,
+        None => false,
+    };
+
+    Ok(RoundResponse {
+        round_id,
+        remaining: stats.remaining(&config),
+        config,
+        stats,
+        open,
+    })
+}
+
+pub fn products(deps: Deps) -> StdResult<Vec<ProductResponse>> {
+    PRODUCTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (product_id, product) = item?;
+            Ok(ProductResponse {
+                product_id,
+                product,
+            })
+        })
+        .collect()
+}
+
+fn collection_response(collection_id: u32, collection: Collection) -> CollectionResponse {
+    CollectionResponse {
+        collection_id,
+        remaining: collection.supply.saturating_sub(collection.minted),
+        collection,
+    }
+}
+
+pub fn collection(deps: Deps, collection_id: u32) -> StdResult<CollectionResponse> {
+    let collection = COLLECTIONS.load(deps.storage, collection_id)?;
+
+    Ok(collection_response(collection_id, collection))
+}
+
+pub fn collections(deps: Deps) -> StdResult<Vec<CollectionResponse>> {
+    COLLECTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (collection_id, collection) = item?;
+            Ok(collection_response(collection_id, collection))
+        })
+        .collect()
+}
+
+pub fn auctions(deps: Deps) -> StdResult<Vec<AuctionResponse>> {
+    AUCTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, auction)) if auction.settled))
+        .map(|item| {
+            let (auction_id, auction) = item?;
+            Ok(AuctionResponse {
+                auction_id,
+                auction,
+            })
+        })
+        .collect()
+}
+
+pub fn auction_bids(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Bid>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    AUCTION_BIDS
+        .prefix(auction_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, bid)| bid))
+        .collect()
+}
+
+pub fn sealed_auctions(deps: Deps) -> StdResult<Vec<SealedAuctionResponse>> {
+    SEALED_AUCTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, auction)) if auction.settled))
+        .map(|item| {
+            let (auction_id, auction) = item?;
+            Ok(SealedAuctionResponse {
+                auction_id,
+                auction,
+            })
+        })
+        .collect()
+}
+
+pub fn sealed_bids(deps: Deps, auction_id: u64) -> StdResult<Vec<SealedBidResponse>> {
+    SEALED_BIDS
+        .prefix(auction_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (bidder, bid) = item?;
+            Ok(SealedBidResponse { bidder, bid })
+        })
+        .collect()
+}
+
+pub fn lottery(deps: Deps) -> StdResult<Option<LotteryResponse>> {
+    let lottery_id = LOTTERY_COUNT.load(deps.storage)?;
+    Ok(LOTTERIES
+        .may_load(deps.storage, lottery_id)?
+        .map(|lottery| LotteryResponse {
+            lottery_id,
+            lottery,
+        }))
+}
+
+pub fn lottery_entry(
+    deps: Deps,
+    lottery_id: u64,
+    address: String,
+) -> StdResult<Option<LotteryEntry>> {
+    let address = deps.api.addr_validate(&address)?;
+    LOTTERY_ENTRIES.may_load(deps.storage, (lottery_id, address))
+}
+
+pub fn inventory(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<InventoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = match start_after {
+        Some(token_id) => Some(INVENTORY_DEPOSITS.load(deps.storage, &token_id)?),
+        None => None,
+    };
+    Ok(InventoryResponse {
+        mode: INVENTORY_MODE.load(deps.storage)?,
+        token_ids: INVENTORY
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, token_id)| token_id))
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn plots(
+    deps: Deps,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PlotResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    PLOTS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (token_id, plot) = item?;
+            Ok(PlotResponse { token_id, plot })
+        })
+        .collect()
+}
+
+pub fn fiat_order(deps: Deps, order_id: String) -> StdResult<Option<FiatOrder>> {
+    FIAT_ORDERS.may_load(deps.storage, &order_id)
+}
+
+pub fn fulfillers(deps: Deps) -> StdResult<Vec<Addr>> {
+    let mut wallets: Vec<Addr> = FULFILLER_WALLETS.load(deps.storage)?.into_iter().collect();
+    wallets.sort();
+
+    Ok(wallets)
+}
+
+pub fn waitlist(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<WaitlistEntryResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    WAITLIST
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (entry_id, entry) = item?;
+            Ok(WaitlistEntryResponse { entry_id, entry })
+        })
+        .collect()
+}
+
+pub fn gating_contracts(deps: Deps) -> StdResult<Vec<Addr>> {
+    GATING_CONTRACTS.load(deps.storage)
+}
+
+pub fn gating_token_usage(
+    deps: Deps,
+    contract: String,
+    token_id: String,
+) -> StdResult<Option<Addr>> {
+    let contract = deps.api.addr_validate(&contract)?;
+    USED_GATING_TOKENS.may_load(deps.storage, (contract, token_id))
+}
+
+pub fn loyalty(deps: Deps) -> StdResult<LoyaltyConfig> {
+    LOYALTY.load(deps.storage)
+}
+
+pub fn loyalty_token_usage(
+    deps: Deps,
+    contract: String,
+    token_id: String,
+) -> StdResult<Option<Addr>> {
+    let contract = deps.api.addr_validate(&contract)?;
+    USED_LOYALTY_TOKENS.may_load(deps.storage, (contract, token_id))
+}
+
+pub fn price_for(
+    deps: Deps,
+    env: Env,
+    address: String,
+    quantity: u32,
+) -> StdResult<PriceForResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    // An open round replaces the sell price, without holder discount
+    let round = match CURRENT_ROUND.load(deps.storage)? {
+        Some(current) if current.open => Some(ROUNDS.load(deps.storage, current.round_id)?),
+        _ => None,
+    };
+    let (nb_held, discount, mut unit_price) = match round {
+        Some(round) => (0, Decimal::zero(), round.price),
+        None => {
+            let (tokens, discount) = loyalty_discount(deps, &address)?;
+            (
+                tokens.len() as u32,
+                discount,
+                STATE.load(deps.storage)?.sell_price,
+            )
+        }
+    };
+    unit_price.amount -= unit_price.amount * discount;
+
+    // Converted at the current oracle rate
+    let unit_price = convert_price(deps, &env, unit_price)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let mut price = unit_price.clone();
+    price.amount *= Uint128::from(quantity);
+
+    Ok(PriceForResponse {
+        nb_held,
+        discount,
+        unit_price,
+        price,
+    })
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -24,6 +24,9 @@ pub enum ContractError {
     #[error("Multibuy quantity too high>")]
     MultiBuyQuantityTooHigh {},
 
+    #[error("Quantity must be greater than 0")]
+    InvalidQuantity {},
+
     #[error("Invalid address {address:?}")]
     InvalidAddress { address: String },
 
@@ -33,6 +36,270 @@ pub enum ContractError {
     #[error("Address already registred {address:?}")]
     AddressAlreadyRegistered { address: String },
 
+    #[error("Invalid template weight")]
+    InvalidTemplateWeight {},
+
+    #[error("Metadata template not found {template_id:?}")]
+    MetadataTemplateNotFound { template_id: u32 },
+
+    #[error("No metadata template left")]
+    NoMetadataTemplateLeft {},
+
+    #[error("Invalid hash {hash:?}")]
+    InvalidHash { hash: String },
+
+    #[error("Provenance already locked, tokens have been minted")]
+    ProvenanceLocked {},
+
+    #[error("Provenance not committed")]
+    ProvenanceNotCommitted {},
+
+    #[error("Provenance hash mismatch")]
+    ProvenanceHashMismatch {},
+
+    #[error("Item hashes don't cover the total supply")]
+    ProvenanceIncomplete {},
+
+    #[error("Seed doesn't match the provenance seed hash")]
+    ProvenanceSeedMismatch {},
+
+    #[error("Sale must be closed to reveal")]
+    RevealDuringSale {},
+
+    #[error("Tokens already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Tokens not revealed yet")]
+    NotRevealed {},
+
+    #[error("Invalid reveal metadata for token {token_id:?}")]
+    InvalidRevealMetadata { token_id: u32 },
+
+    #[error("Invalid public key")]
+    InvalidPublicKey {},
+
+    #[error("Permit signer not set")]
+    PermitSignerNotSet {},
+
+    #[error("Invalid permit")]
+    InvalidPermit {},
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
+
+    #[error("Permit expired")]
+    PermitExpired {},
+
+    #[error("Permit nonce already used {nonce:?}")]
+    PermitNonceUsed { nonce: u64 },
+
+    #[error("Permit quantity exceeded")]
+    PermitQuantityExceeded {},
+
+    #[error("Invalid referral share")]
+    InvalidReferralShare {},
+
+    #[error("Self referral is not allowed")]
+    SelfReferral {},
+
+    #[error("No referral rewards to claim")]
+    NoReferralRewards {},
+
+    #[error("Invalid discount")]
+    InvalidDiscount {},
+
+    #[error("Voucher already exists")]
+    VoucherAlreadyExists {},
+
+    #[error("Voucher not found")]
+    VoucherNotFound {},
+
+    #[error("Voucher expired")]
+    VoucherExpired {},
+
+    #[error("Voucher fully redeemed")]
+    VoucherExhausted {},
+
+    #[error("Voucher not allowed for this address")]
+    VoucherNotAllowed {},
+
+    #[error("Recipients count must match the quantity")]
+    InvalidRecipients {},
+
+    #[error("Address {address:?} is not a treasury wallet")]
+    NotTreasuryWallet { address: String },
+
+    #[error("Insufficient funds, {available:?} available")]
+    InsufficientFunds { available: Coin },
+
+    #[error("Invalid withdraw threshold")]
+    InvalidWithdrawThreshold {},
+
+    #[error("Withdraw proposal {proposal_id:?} not found")]
+    WithdrawProposalNotFound { proposal_id: u64 },
+
+    #[error("Withdraw proposal {proposal_id:?} expired")]
+    WithdrawProposalExpired { proposal_id: u64 },
+
+    #[error("Withdraw proposal {proposal_id:?} already approved")]
+    WithdrawAlreadyApproved { proposal_id: u64 },
+
+    #[error("Pending change {change_id:?} not found")]
+    PendingChangeNotFound { change_id: u64 },
+
+    #[error("Pending change {change_id:?} not active before {activation:?}")]
+    PendingChangeNotReady { change_id: u64, activation: u64 },
+
+    #[error("Invalid supply move")]
+    InvalidSupplyMove {},
+
+    #[error("Invalid round")]
+    InvalidRound {},
+
+    #[error("Round {round_id:?} not found")]
+    RoundNotFound { round_id: u32 },
+
+    #[error("Round {round_id:?} is open")]
+    RoundOpen { round_id: u32 },
+
+    #[error("Round not started")]
+    RoundNotStarted {},
+
+    #[error("Round ended")]
+    RoundEnded {},
+
+    #[error("Round wallet limit reached")]
+    RoundWalletLimitReached {},
+
+    #[error("Product {product_id:?} not found")]
+    ProductNotFound { product_id: u32 },
+
+    #[error("Collection {collection_id:?} not found")]
+    CollectionNotFound { collection_id: u32 },
+
+    #[error("A product can't be bought from a collection")]
+    ProductNotInCollection {},
+
+    #[error("The NFT contract of a collection can't change once minted")]
+    CollectionNftContractLocked {},
+
+    #[error("NFT contract {address:?} already used by another sale")]
+    NftContractInUse { address: String },
+
+    #[error("Auction {auction_id:?} not found")]
+    AuctionNotFound { auction_id: u64 },
+
+    #[error("Invalid auction")]
+    InvalidAuction {},
+
+    #[error("Auction not started")]
+    AuctionNotStarted {},
+
+    #[error("Auction ended")]
+    AuctionEnded {},
+
+    #[error("Auction not ended")]
+    AuctionNotEnded {},
+
+    #[error("Auction has bids")]
+    AuctionHasBids {},
+
+    #[error("Bid too low, the minimum bid is {min_bid:?}")]
+    BidTooLow { min_bid: Coin },
+
+    #[error("A bid is paid with a single coin of the auction denom")]
+    InvalidBidFunds {},
+
+    #[error("Commit phase ended")]
+    CommitPhaseEnded {},
+
+    #[error("Reveal phase not started")]
+    RevealPhaseNotStarted {},
+
+    #[error("Sealed bid already committed")]
+    SealedBidAlreadyCommitted {},
+
+    #[error("Sealed bid not found")]
+    SealedBidNotFound {},
+
+    #[error("Sealed bid doesn't match the commitment")]
+    SealedBidMismatch {},
+
+    #[error("Deposit doesn't cover the bid")]
+    InsufficientDeposit {},
+
+    #[error("Lottery not found")]
+    LotteryNotFound {},
+
+    #[error("Lottery already created")]
+    LotteryAlreadyCreated {},
+
+    #[error("Invalid lottery")]
+    InvalidLottery {},
+
+    #[error("Lottery registration not open")]
+    RegistrationNotOpen {},
+
+    #[error("Lottery registration not ended")]
+    RegistrationNotEnded {},
+
+    #[error("Already registered to the lottery")]
+    LotteryAlreadyRegistered {},
+
+    #[error("Not registered to the lottery")]
+    LotteryEntryNotFound {},
+
+    #[error("Lottery already drawn")]
+    LotteryAlreadyDrawn {},
+
+    #[error("Lottery not drawn")]
+    LotteryNotDrawn {},
+
+    #[error("Lottery draw deadline passed")]
+    LotteryDrawExpired {},
+
+    #[error("No lottery tickets left")]
+    LotteryFull {},
+
+    #[error("Seed doesn't match the lottery seed hash")]
+    LotterySeedMismatch {},
+
+    #[error("Token {token_id:?} not in inventory")]
+    InventoryTokenNotFound { token_id: String },
+
+    #[error("Plot {token_id:?} not found")]
+    PlotNotFound { token_id: u32 },
+
+    #[error("Plot {token_id:?} already sold")]
+    PlotSold { token_id: u32 },
+
+    #[error("Token id {token_id:?} already taken")]
+    PlotIdTaken { token_id: u32 },
+
+    #[error("Fiat order {order_id:?} already fulfilled")]
+    FiatOrderAlreadyFulfilled { order_id: String },
+
+    #[error("Tokens are still available")]
+    SupplyAvailable {},
+
+    #[error("Waitlist entry {entry_id:?} not found")]
+    WaitlistEntryNotFound { entry_id: u64 },
+
+    #[error("The waitlist must be fulfilled first")]
+    WaitlistPending {},
+
+    #[error("Invalid loyalty tier")]
+    InvalidLoyaltyTier {},
+
+    #[error("Oracle price too old")]
+    OraclePriceStale {},
+
+    #[error("Oracle price deviates too much from the check oracle")]
+    OraclePriceDeviation {},
+
+    #[error("Price {price} above the maximum price")]
+    PriceAboveMax { price: Coin },
+
     #[error("Not enough Money")]
     NotEnoughMoneyForNft {}, // Add any other custom errors you like here.
                              // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
@@ -1,4 +1,11 @@
-use cosmwasm_std::{Addr, Coin};
+use crate::state::{
+    Auction, AuctionConfig, Collection, ConfigChange, Discount, GeneratedAttribute, InventoryOrder,
+    Lottery, LotteryConfig, LoyaltyTier, Plot, PlotEntry, Product, RoundConfig, RoundStats,
+    SealedAuction, SealedAuctionConfig, SealedBid, SupplyKind, TokenID, Voucher, WaitlistEntry,
+    WithdrawProposal,
+};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
+use cw721::Cw721ReceiveMsg;
 use cw_carbonable_lib::Metadata;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,6 +23,57 @@ pub struct WhiteListEntry {
     pub nb_slots: u32,
 }
 
+/// Purchase permit, issued off-chain by the permit signer
+///
+/// The signature is the secp256k1 signature (64 bytes, r || s) of the sha256 of
+/// the JSON serialization of the matching `PermitPayload`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permit {
+    pub buyer: String,
+    pub max_quantity: u32,
+    pub price: Coin,
+    pub expires: u64,
+    pub nonce: u64,
+    pub signature: Binary,
+}
+
+/// Signed content of a permit
+///
+/// `contract` is the sell contract address, so a permit can't be replayed on another sale.
+/// `price` is the price of a single token, `expires` is a block time in seconds.
+/// `product_id` and `collection_id` are the product or the collection bought with the permit,
+/// none for the main sale.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitPayload {
+    pub contract: String,
+    pub buyer: String,
+    pub max_quantity: u32,
+    pub price: Coin,
+    pub expires: u64,
+    pub nonce: u64,
+    pub product_id: Option<u32>,
+    pub collection_id: Option<u32>,
+}
+
+/// Committed content of a sealed bid
+///
+/// `price` is the price of a single token, `salt` is a random string kept secret until the
+/// reveal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedBidPayload {
+    pub contract: String,
+    pub auction_id: u64,
+    pub bidder: String,
+    pub price: Uint128,
+    pub salt: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RevealEntry {
+    pub token_id: u32,
+    pub metadata: Metadata,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -23,26 +81,133 @@ pub enum ExecuteMsg {
         reserved_supply: u32,
         market_supply: u32,
     },
+    MoveSupply {
+        from: SupplyKind,
+        to: SupplyKind,
+        amount: u32,
+    },
+    UpdateAutoReleaseReserved {
+        enable: bool,
+    },
     UpdatePrice {
         price: Coin,
     },
     UpdateNftContract {
         address: String,
     },
+    UpdateTimelockDelay {
+        delay: u64,
+    },
+    ExecutePendingChange {
+        change_id: u64,
+    },
+    CancelPendingChange {
+        change_id: u64,
+    },
     UpdateMetadata {
         metadata: Metadata,
     },
-    Buy {},
+    UpdateGeneratedAttributes {
+        attributes: Vec<GeneratedAttribute>,
+    },
+    SetMetadataTemplate {
+        template_id: u32,
+        metadata: Metadata,
+        weight: u32,
+        supply: u32,
+    },
+    RemoveMetadataTemplate {
+        template_id: u32,
+    },
+
+    /// Delayed reveal
+    CommitProvenance {
+        provenance_hash: String,
+        seed_hash: String,
+    },
+    Reveal {
+        item_hashes: Vec<String>,
+        seed: String,
+    },
+    RevealBatch {
+        entries: Vec<RevealEntry>,
+    },
+    Buy {
+        permit: Option<Permit>,
+        referrer: Option<String>,
+        voucher: Option<String>,
+        recipient: Option<String>,
+        product_id: Option<u32>,
+        collection_id: Option<u32>,
+        max_price: Option<Coin>,
+    },
+    JoinWaitlist {
+        quantity: u32,
+        max_price: Option<Coin>,
+    },
+    LeaveWaitlist {
+        entry_id: u64,
+    },
+    FulfillWaitlist {
+        limit: Option<u32>,
+    },
+    BuySpecific {
+        token_ids: Vec<u32>,
+        max_price: Option<Coin>,
+    },
     MultiBuy {
         quantity: u32,
+        permit: Option<Permit>,
+        referrer: Option<String>,
+        voucher: Option<String>,
+        recipients: Option<Vec<String>>,
+        product_id: Option<u32>,
+        collection_id: Option<u32>,
+        max_price: Option<Coin>,
+    },
+    UpdateReferralShare {
+        share: Decimal,
+    },
+    ClaimReferralRewards {},
+
+    /// Vouchers, identified by the hex sha256 of their code
+    CreateVoucher {
+        code_hash: String,
+        discount: Discount,
+        max_redemptions: u32,
+        expires: Option<u64>,
+        allowed_address: Option<String>,
+    },
+    RemoveVoucher {
+        code_hash: String,
     },
     Airdrop {
         receivers: Vec<String>,
     },
     Withdraw {
-        wallet: Addr,
+        wallet: String,
         coin: Vec<Coin>,
     },
+    WithdrawAll {
+        wallet: String,
+        denom: String,
+    },
+    AddTreasuryWallet {
+        address: String,
+    },
+    RemoveTreasuryWallet {
+        address: String,
+    },
+    ApproveWithdraw {
+        proposal_id: u64,
+    },
+    CancelWithdraw {
+        proposal_id: u64,
+    },
+    UpdateWithdrawConfig {
+        threshold: u32,
+        proposal_duration: u64,
+    },
     PreSellMode {
         enable: bool,
     },
@@ -52,6 +217,120 @@ pub enum ExecuteMsg {
     AddToWhitelist {
         entries: Vec<WhiteListEntry>,
     },
+    UpdateGatingContracts {
+        contracts: Vec<String>,
+    },
+    UpdateLoyalty {
+        contracts: Vec<String>,
+        tiers: Vec<LoyaltyTier>,
+    },
+    UpdateOracle {
+        oracle: Option<OracleConfig>,
+    },
+    SetPermitSigner {
+        pubkey: Binary,
+    },
+    SetProduct {
+        product_id: u32,
+        name: String,
+        price: Coin,
+        supply: u32,
+        metadata: Metadata,
+    },
+    RemoveProduct {
+        product_id: u32,
+    },
+    SetPlots {
+        plots: Vec<PlotEntry>,
+    },
+    RemovePlot {
+        token_id: u32,
+    },
+    SetCollection {
+        collection_id: u32,
+        config: CollectionConfig,
+    },
+    SetRound {
+        round_id: u32,
+        config: RoundConfig,
+    },
+    RemoveRound {
+        round_id: u32,
+    },
+    AddToRoundWhitelist {
+        round_id: u32,
+        addresses: Vec<String>,
+    },
+    ActivateRound {
+        round_id: u32,
+    },
+    CloseRound {},
+
+    /// English auctions
+    CreateAuction {
+        config: AuctionConfig,
+    },
+    CancelAuction {
+        auction_id: u64,
+    },
+    PlaceBid {
+        auction_id: u64,
+    },
+    SettleAuction {
+        auction_id: u64,
+    },
+
+    /// Sealed-bid auctions
+    CreateSealedAuction {
+        config: SealedAuctionConfig,
+    },
+    CommitSealedBid {
+        auction_id: u64,
+        commitment: String,
+    },
+    RevealSealedBid {
+        auction_id: u64,
+        price: Uint128,
+        salt: String,
+    },
+    SettleSealedAuction {
+        auction_id: u64,
+        limit: Option<u32>,
+    },
+    ClaimSealedRefund {
+        auction_id: u64,
+    },
+
+    /// Pre-sale lottery
+    CreateLottery {
+        config: LotteryConfig,
+    },
+    RegisterLottery {
+        quantity: u32,
+    },
+    DrawLottery {
+        seed: String,
+    },
+    ClaimLottery {
+        lottery_id: u64,
+    },
+
+    /// Pre-minted inventory
+    ReceiveNft(Cw721ReceiveMsg),
+    UpdateInventoryMode {
+        mode: Option<InventoryOrder>,
+    },
+    WithdrawInventory {
+        token_ids: Vec<String>,
+        recipient: String,
+    },
+
+    /// Fiat orders
+    FulfillFiatOrder {
+        order_id: String,
+        recipient: String,
+        quantity: u32,
+    },
 
     /// Roles mgmt
     RemoveAdmin {
@@ -60,6 +339,12 @@ pub enum ExecuteMsg {
     AddAdmin {
         address: String,
     },
+    RemoveFulfiller {
+        address: String,
+    },
+    AddFulfiller {
+        address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -67,4 +352,267 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     // Return the contract state
     DumpState {},
+    // Return the attributes generated for each minted token
+    GeneratedAttributes {},
+    // Return the metadata templates with their remaining counts
+    MetadataTemplates {},
+    // Return the delayed reveal provenance, if committed
+    Provenance {},
+    // Return the referral stats of an address
+    ReferralStats {
+        address: String,
+    },
+    // Return a voucher and whether it can still be redeemed
+    Voucher {
+        code_hash: String,
+    },
+    // Return the contract balances, the sales revenue and the treasury wallets
+    Treasury {},
+    // Return the withdrawal approval settings
+    WithdrawConfig {},
+    // Return the pending withdrawal proposals with their approvers
+    WithdrawProposals {},
+    // Return the timelock delay and the queued configuration changes
+    PendingChanges {},
+    // Return the running aggregates of the sale
+    SaleStats {},
+    // Return a sale round with its stats
+    Round {
+        round_id: u32,
+    },
+    // Return the sale rounds with their stats
+    Rounds {},
+    // Return the products
+    Products {},
+    // Return a collection with its sale figures
+    Collection {
+        collection_id: u32,
+    },
+    // Return the collections with their sale figures
+    Collections {},
+    // Return the auctions not settled yet
+    Auctions {},
+    // Return the bid history of an auction, oldest first, bids are numbered from 1
+    AuctionBids {
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Return the sealed-bid auctions not settled yet
+    SealedAuctions {},
+    // Return the sealed bids of an auction, prices are only known once revealed
+    SealedBids {
+        auction_id: u64,
+    },
+    // Return the current pre-sale lottery, with its seed once drawn
+    Lottery {},
+    // Return the lottery registration of an address, with its result once drawn
+    LotteryEntry {
+        lottery_id: u64,
+        address: String,
+    },
+    // Return the inventory mode and the tokens left in the inventory, oldest deposit first
+    Inventory {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Return the plots with their availability, by token id
+    Plots {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    // Return the cw721 contracts gating the pre-sale
+    GatingContracts {},
+    // Return the buyer who used a gating token, if used
+    GatingTokenUsage {
+        contract: String,
+        token_id: String,
+    },
+    // Return the holder loyalty contracts and tiers
+    Loyalty {},
+    // Return the buyer who got a discount with a loyalty token, if used
+    LoyaltyTokenUsage {
+        contract: String,
+        token_id: String,
+    },
+    // Return the price oracle
+    Oracle {},
+    // Return the price an address pays for a quantity of tokens at the sell price, or the
+    // open round price, before vouchers and in the payment denom
+    PriceFor {
+        address: String,
+        quantity: u32,
+    },
+    // Return the waitlist, in FIFO order
+    Waitlist {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Return a fiat order, if fulfilled
+    FiatOrder {
+        order_id: String,
+    },
+    // Return the fulfiller wallets
+    Fulfillers {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedAuctionResponse {
+    pub auction_id: u64,
+    pub auction: SealedAuction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedBidResponse {
+    pub bidder: Addr,
+    pub bid: SealedBid,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InventoryResponse {
+    pub mode: Option<InventoryOrder>,
+    pub token_ids: Vec<TokenID>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionResponse {
+    pub auction_id: u64,
+    pub auction: Auction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LotteryResponse {
+    pub lottery_id: u64,
+    pub lottery: Lottery,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionConfig {
+    pub name: String,
+    pub nft_contract: String,
+    pub price: Coin,
+    pub supply: u32,
+    pub metadata: Metadata,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionResponse {
+    pub collection_id: u32,
+    pub collection: Collection,
+    pub remaining: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OracleConfig {
+    pub contract: String,
+    pub check_contract: String,
+    pub reference_denom: String,
+    pub payment_denom: String,
+    pub max_staleness: u64,
+    pub max_deviation: Decimal,
+}
+
+/// Query interface of the price oracle contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    // Return the price of one `base` unit in `quote` units
+    Price { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePriceResponse {
+    pub rate: Decimal,
+    /// time of the last rate update, in seconds
+    pub updated_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceForResponse {
+    /// tokens held on the loyalty contracts and not used by another buyer, counted until the
+    /// highest tier is reached
+    pub nb_held: u32,
+    pub discount: Decimal,
+    pub unit_price: Coin,
+    pub price: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WaitlistEntryResponse {
+    pub entry_id: u64,
+    pub entry: WaitlistEntry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlotResponse {
+    pub token_id: u32,
+    pub plot: Plot,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProductResponse {
+    pub product_id: u32,
+    pub product: Product,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundResponse {
+    pub round_id: u32,
+    pub config: RoundConfig,
+    pub stats: RoundStats,
+    pub remaining: u32,
+    pub open: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleStatsResponse {
+    pub revenue: Vec<Coin>,
+    pub nb_buyers: u32,
+    pub pre_sale_tokens: u32,
+    pub public_sale_tokens: u32,
+    pub airdrop_tokens: u32,
+    /// tokens bought, pre and public sale, per unique buyer
+    pub average_tokens_per_buyer: Decimal,
+    pub first_sale: Option<u64>,
+    pub last_sale: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingChangeResponse {
+    pub change_id: u64,
+    pub change: ConfigChange,
+    pub activation: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingChangesResponse {
+    pub delay: u64,
+    pub changes: Vec<PendingChangeResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawProposalResponse {
+    pub proposal_id: u64,
+    pub proposal: WithdrawProposal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryResponse {
+    pub balances: Vec<Coin>,
+    pub revenue: Vec<Coin>,
+    pub wallets: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherResponse {
+    pub voucher: Voucher,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MetadataTemplateResponse {
+    pub template_id: u32,
+    pub metadata: Metadata,
+    pub weight: u32,
+    pub remaining: u32,
 }
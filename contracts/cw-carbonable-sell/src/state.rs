@@ -2,7 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
 use cw_carbonable_lib::Metadata;
 use cw_storage_plus::{Item, Map};
 
@@ -33,11 +33,18 @@ pub const OWNER_WALLET: Item<Addr> = Item::new("owner_wallet");
 /// Address of admins wallet
 ///
 /// These user have these differents role
-/// * add/remove admin wallets
 /// * add/remove nft id in nft map
-/// * withdraw from smartcontract wallet
+/// * propose and approve withdrawals from smartcontract wallet
+///
+/// Only the owner adds and removes admin wallets, so that one admin can't reach the
+/// withdraw threshold alone
 pub const ADMIN_WALLETS: Item<HashSet<Addr>> = Item::new("admin_wallets");
 
+/// Address of fulfiller wallets
+///
+/// Web shop backends, they deliver the orders paid off-chain
+pub const FULFILLER_WALLETS: Item<HashSet<Addr>> = Item::new("fulfiller_wallets");
+
 /// Address of carbonable NFT contract
 ///
 /// These user have this role
@@ -52,3 +59,620 @@ pub const PRE_SELL_MODE: Item<bool> = Item::new("pre_sell_mode");
 
 /// WhiteList map
 pub const WHITELIST: Map<Addr, u32> = Map::new("whitelist");
+
+/// Attribute generated for each token at mint time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratedAttribute {
+    /// token id of the minted token
+    SerialNumber,
+    /// "n of N" where N is the total supply (market + reserved)
+    Edition,
+    /// pre_sale, public_sale or airdrop
+    MintPhase,
+    /// block time of the mint, in seconds
+    MintDate,
+    /// market or reserved
+    BuyerType,
+}
+
+/// Phase in which a token has been minted
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MintPhase {
+    PreSale,
+    PublicSale,
+    Airdrop,
+}
+
+impl MintPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MintPhase::PreSale => "pre_sale",
+            MintPhase::PublicSale => "public_sale",
+            MintPhase::Airdrop => "airdrop",
+        }
+    }
+
+    /// Supply the token is minted from
+    pub fn buyer_type(&self) -> &'static str {
+        match self {
+            MintPhase::Airdrop => "reserved",
+            _ => "market",
+        }
+    }
+}
+
+/// Generated attributes
+///
+/// Appended, in this order, to the metadata attributes of every minted token
+pub const GENERATED_ATTRIBUTES: Item<Vec<GeneratedAttribute>> = Item::new("generated_attributes");
+
+/// Metadata template
+///
+/// When templates are registered, each bought token gets the metadata of a
+/// template picked at random, weighted by `weight`, among the templates with
+/// `remaining` > 0.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MetadataTemplate {
+    pub metadata: Metadata,
+    pub weight: u32,
+    pub remaining: u32,
+}
+
+/// Metadata templates map, by template id
+pub const METADATA_TEMPLATES: Map<u32, MetadataTemplate> = Map::new("metadata_templates");
+
+/// Provenance of a delayed reveal
+///
+/// Until the reveal, tokens are minted with the (placeholder) project metadata.
+/// `provenance_hash` is committed before the sale: it is the hex sha256 of the
+/// concatenation of the hex sha256 of each final metadata JSON, in collection order.
+/// `seed_hash`, the hex sha256 of a secret seed, is committed with it.
+/// After the sale, the item hashes and the seed are published and the offset is drawn:
+/// `offset = lottery_random(seed, last token id) % item_hashes.len()`, token `n` (starting
+/// at 1) gets the final metadata at index `(n - 1 + offset) % item_hashes.len()`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Provenance {
+    pub provenance_hash: String,
+    pub seed_hash: String,
+    pub item_hashes: Vec<String>,
+    /// revealed with the item hashes
+    pub seed: Option<String>,
+    pub offset: Option<u32>,
+}
+
+pub const PROVENANCE: Item<Provenance> = Item::new("provenance");
+
+/// Public key (secp256k1, compressed or uncompressed) of the backend signing purchase permits
+pub const PERMIT_SIGNER: Item<Binary> = Item::new("permit_signer");
+
+/// Permit nonces already used
+pub const USED_PERMIT_NONCES: Map<u64, bool> = Map::new("used_permit_nonces");
+
+/// Share of the payment credited to the referrer of a purchase
+pub const REFERRAL_SHARE: Item<Decimal> = Item::new("referral_share");
+
+/// Referral stats of a referrer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct ReferralStats {
+    pub nb_purchases: u32,
+    pub nb_tokens: u32,
+    pub total_earned: Vec<Coin>,
+    pub claimable: Vec<Coin>,
+}
+
+/// Referral stats map, by referrer
+pub const REFERRALS: Map<Addr, ReferralStats> = Map::new("referrals");
+
+/// Discount granted by a voucher, on each token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Discount {
+    /// share of the price taken off, between 0 and 1
+    Percent(Decimal),
+    /// amount taken off, in the payment denom
+    Fixed(Uint128),
+}
+
+/// Discount voucher
+///
+/// A redemption is a whole Buy/MultiBuy, the discount applies to each token bought.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Voucher {
+    pub discount: Discount,
+    pub max_redemptions: u32,
+    pub redemptions: u32,
+    pub expires: Option<u64>,
+    pub allowed_address: Option<Addr>,
+}
+
+/// Vouchers map, by hex sha256 of the voucher code
+pub const VOUCHERS: Map<&str, Voucher> = Map::new("vouchers");
+
+/// Destinations allowed for withdrawals
+///
+/// Managed by the owner only, the owner wallet is registered at instantiation.
+pub const TREASURY_WALLETS: Item<HashSet<Addr>> = Item::new("treasury_wallets");
+
+/// Running aggregates of the sale
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct SaleStats {
+    /// total sales revenue, by denom
+    pub revenue: Vec<Coin>,
+    pub nb_buyers: u32,
+    pub pre_sale_tokens: u32,
+    pub public_sale_tokens: u32,
+    pub airdrop_tokens: u32,
+    /// time in seconds of the first and last Buy/MultiBuy
+    pub first_sale: Option<u64>,
+    pub last_sale: Option<u64>,
+}
+
+pub const SALE_STATS: Item<SaleStats> = Item::new("sale_stats");
+
+/// Tokens bought, by payer
+pub const BUYERS: Map<Addr, u32> = Map::new("buyers");
+
+/// Withdrawal approval settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawConfig {
+    /// distinct approvals among the owner and the admins needed to send the funds
+    pub threshold: u32,
+    /// seconds a proposal stays open
+    pub proposal_duration: u64,
+}
+
+pub const WITHDRAW_CONFIG: Item<WithdrawConfig> = Item::new("withdraw_config");
+
+/// Funds requested by a withdrawal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawAmount {
+    Coins(Vec<Coin>),
+    /// whole available balance of a denom, computed when the funds are sent
+    All(String),
+}
+
+/// Pending withdrawal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawProposal {
+    pub proposer: Addr,
+    pub wallet: Addr,
+    pub amount: WithdrawAmount,
+    pub approvers: Vec<Addr>,
+    pub expires: u64,
+}
+
+/// Pending withdrawals map, by proposal id
+pub const WITHDRAW_PROPOSALS: Map<u64, WithdrawProposal> = Map::new("withdraw_proposals");
+
+/// Last withdrawal proposal id
+pub const WITHDRAW_PROPOSAL_COUNT: Item<u64> = Item::new("withdraw_proposal_count");
+
+/// Seconds between the request of a sensitive change and its activation
+///
+/// Zero applies the changes right away.
+pub const TIMELOCK_DELAY: Item<u64> = Item::new("timelock_delay");
+
+/// Sensitive configuration change, subject to the timelock
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChange {
+    Price(Coin),
+    Supply {
+        reserved_supply: u32,
+        market_supply: u32,
+    },
+    NftContract(Addr),
+    AddTreasuryWallet(Addr),
+    RemoveTreasuryWallet(Addr),
+    TimelockDelay(u64),
+    /// secp256k1 public key signing the purchase permits
+    PermitSigner(Binary),
+    MoveSupply {
+        from: SupplyKind,
+        to: SupplyKind,
+        amount: u32,
+    },
+    Round {
+        round_id: u32,
+        config: RoundConfig,
+    },
+    /// minted is kept from the current product
+    Product {
+        product_id: u32,
+        product: Product,
+    },
+    /// minted, last token id and revenue are kept from the current collection
+    Collection {
+        collection_id: u32,
+        collection: Collection,
+    },
+    /// sold plots can't change
+    Plots(Vec<PlotEntry>),
+    /// none disables the price conversion
+    Oracle(Option<Oracle>),
+}
+
+/// Queued configuration change
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingChange {
+    pub change: ConfigChange,
+    /// time in seconds from which the change can be executed
+    pub activation: u64,
+}
+
+/// Queued changes map, by change id
+pub const PENDING_CHANGES: Map<u64, PendingChange> = Map::new("pending_changes");
+
+/// Last queued change id
+pub const PENDING_CHANGE_COUNT: Item<u64> = Item::new("pending_change_count");
+
+/// Supply tokens are minted from
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyKind {
+    /// sold through Buy and MultiBuy
+    Market,
+    /// airdropped
+    Reserved,
+}
+
+/// Release the unminted reserved supply into the market supply when the public sale is closed,
+/// as a timelocked `ConfigChange::MoveSupply`
+pub const AUTO_RELEASE_RESERVED: Item<bool> = Item::new("auto_release_reserved");
+
+/// Sale round settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundConfig {
+    pub name: String,
+    pub price: Coin,
+    /// tokens sold in the round, on top of the supply carried from the previous round
+    pub supply: u32,
+    /// time window in seconds, start included, end excluded
+    pub start: u64,
+    pub end: u64,
+    /// only the addresses of the round whitelist can buy
+    pub whitelist_only: bool,
+    pub max_per_wallet: Option<u32>,
+    /// unsold supply is added to the next activated round
+    pub carry_forward: bool,
+}
+
+/// Sale rounds map, by round id
+pub const ROUNDS: Map<u32, RoundConfig> = Map::new("rounds");
+
+/// Sale round stats
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RoundStats {
+    pub nb_tokens: u32,
+    pub nb_buyers: u32,
+    pub revenue: Vec<Coin>,
+    /// unsold supply received from the previous round
+    pub carried_in: u32,
+    /// unsold supply given to the next round
+    pub carried_out: u32,
+}
+
+impl RoundStats {
+    /// Tokens left for sale in the round
+    pub fn remaining(&self, config: &RoundConfig) -> u32 {
+        (config.supply + self.carried_in).saturating_sub(self.nb_tokens + self.carried_out)
+    }
+}
+
+/// Sale round stats map, by round id
+pub const ROUND_STATS: Map<u32, RoundStats> = Map::new("round_stats");
+
+/// Round whitelists, by round id and address
+pub const ROUND_WHITELIST: Map<(u32, Addr), bool> = Map::new("round_whitelist");
+
+/// Tokens bought in a round, by round id and payer
+pub const ROUND_PURCHASES: Map<(u32, Addr), u32> = Map::new("round_purchases");
+
+/// Last activated round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrentRound {
+    pub round_id: u32,
+    /// an open round replaces the sell modes, the whitelist and the sell price
+    pub open: bool,
+}
+
+pub const CURRENT_ROUND: Item<Option<CurrentRound>> = Item::new("current_round");
+
+/// Product sold alongside the others, with its own price, supply and metadata
+///
+/// Products are sold from the market supply.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Product {
+    pub name: String,
+    pub price: Coin,
+    pub supply: u32,
+    pub minted: u32,
+    pub metadata: Metadata,
+}
+
+/// Products map, by product id
+pub const PRODUCTS: Map<u32, Product> = Map::new("products");
+
+/// Collection sold from its own NFT contract, with its own price, supply and metadata
+///
+/// Collections don't use the market supply, whitelists and admins are shared.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Collection {
+    pub name: String,
+    pub nft_contract: Addr,
+    pub price: Coin,
+    pub supply: u32,
+    pub minted: u32,
+    pub last_token_id: u32,
+    pub metadata: Metadata,
+    pub revenue: Vec<Coin>,
+}
+
+/// Collections map, by collection id
+pub const COLLECTIONS: Map<u32, Collection> = Map::new("collections");
+
+/// English auction settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionConfig {
+    pub metadata: Metadata,
+    /// lowest accepted first bid, its denom is the auction denom
+    pub reserve_price: Coin,
+    /// a new bid is at least the highest bid plus this increment, not zero
+    pub min_increment: Uint128,
+    /// time window in seconds, start included, end excluded
+    pub start: u64,
+    pub end: u64,
+    /// a bid placed less than `extension` seconds before the end pushes the end back
+    pub extension: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Coin,
+    pub time: u64,
+}
+
+/// Auction of a single token, the token is taken from the market supply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    pub config: AuctionConfig,
+    /// end of the auction, including the anti-sniping extensions
+    pub end: u64,
+    /// escrowed until outbid or settled
+    pub highest_bid: Option<Bid>,
+    pub nb_bids: u64,
+    pub settled: bool,
+}
+
+/// Auctions map, by auction id
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+
+/// Bid history, by auction id and bid number, counting from 1
+pub const AUCTION_BIDS: Map<(u64, u64), Bid> = Map::new("auction_bids");
+
+/// Sealed-bid auction settings
+///
+/// Each commitment is a bid for one token. The `quantity` highest revealed bids win, all
+/// winners pay the lowest winning bid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedAuctionConfig {
+    pub metadata: Metadata,
+    pub denom: String,
+    pub reserve_price: Uint128,
+    pub quantity: u32,
+    /// commitments are accepted from `commit_start` to `commit_end`, then revealed until
+    /// `reveal_end`, start included, end excluded
+    pub commit_start: u64,
+    pub commit_end: u64,
+    pub reveal_end: u64,
+}
+
+/// Sealed-bid auction, the tokens are taken from the market supply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedAuction {
+    pub config: SealedAuctionConfig,
+    pub nb_commitments: u32,
+    pub nb_reveals: u32,
+    /// best revealed bids, highest first, at most `quantity`
+    pub winners: Vec<SealedWinner>,
+    /// winners minted so far, the settlement runs in batches
+    pub nb_settled: u32,
+    pub clearing_price: Option<Coin>,
+    pub settled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedWinner {
+    pub bidder: Addr,
+    pub price: Uint128,
+    pub committed: u64,
+}
+
+/// Sealed-bid auctions map, by auction id, ids are shared with the English auctions
+pub const SEALED_AUCTIONS: Map<u64, SealedAuction> = Map::new("sealed_auctions");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SealedBid {
+    /// hex sha256 of the JSON serialization of the matching `SealedBidPayload`
+    pub commitment: String,
+    /// escrowed until settled or refunded, must cover the reserve and the revealed price
+    pub deposit: Coin,
+    pub committed: u64,
+    pub price: Option<Uint128>,
+}
+
+/// Sealed bids map, by auction id and bidder, removed once settled or refunded
+pub const SEALED_BIDS: Map<(u64, Addr), SealedBid> = Map::new("sealed_bids");
+
+/// Pre-sale lottery settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LotteryConfig {
+    pub price: Coin,
+    pub supply: u32,
+    /// registration window in seconds, start included, end excluded
+    pub registration_start: u64,
+    pub registration_end: u64,
+    /// hex sha256 of the draw seed, the seed is kept secret until the draw
+    pub seed_hash: String,
+    /// without a draw before this time, the entries are refunded in full
+    pub draw_deadline: u64,
+    /// a new lottery can be created after this time, the entries stay claimable
+    pub claim_deadline: u64,
+}
+
+/// Pre-sale lottery, the tokens are taken from the market supply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Lottery {
+    pub config: LotteryConfig,
+    pub nb_entrants: u32,
+    /// one ticket per requested token
+    pub nb_tickets: u32,
+    pub nb_winning_tickets: u32,
+    /// revealed at the draw
+    pub seed: Option<String>,
+    /// the tokens not won are back in the market supply
+    pub closed: bool,
+    pub nb_claimed: u32,
+}
+
+/// Lotteries map, by lottery id, the last one is the current lottery
+pub const LOTTERIES: Map<u64, Lottery> = Map::new("lotteries");
+pub const LOTTERY_COUNT: Item<u64> = Item::new("lottery_count");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LotteryEntry {
+    pub quantity: u32,
+    /// escrowed until claimed
+    pub deposit: Coin,
+    pub won: u32,
+}
+
+/// Lottery registrations, by lottery id and address, removed once claimed
+pub const LOTTERY_ENTRIES: Map<(u64, Addr), LotteryEntry> = Map::new("lottery_entries");
+
+/// Order in which pre-minted tokens leave the inventory
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InventoryOrder {
+    /// oldest deposit first
+    Sequential,
+    /// pseudo-random, see `take_from_inventory`: predictable and not uniform, it only mixes
+    /// the tokens, it is not a fair draw
+    Random,
+}
+
+/// Inventory mode, when set the sale transfers pre-minted tokens instead of minting them
+///
+/// Products and collections are still minted on demand.
+pub const INVENTORY_MODE: Item<Option<InventoryOrder>> = Item::new("inventory_mode");
+
+/// Pre-minted tokens held by the contract, by deposit number
+pub const INVENTORY: Map<u64, TokenID> = Map::new("inventory");
+pub const INVENTORY_COUNT: Item<u64> = Item::new("inventory_count");
+
+/// Deposit number of the pre-minted tokens, by token id
+pub const INVENTORY_DEPOSITS: Map<&str, u64> = Map::new("inventory_deposits");
+
+/// Plot sold under its own token id, with its own price and metadata
+///
+/// Plots are taken from the market supply, sequential token ids skip the plot ids.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Plot {
+    pub price: Coin,
+    pub metadata: Metadata,
+    pub sold: bool,
+}
+
+/// Plots map, by token id
+pub const PLOTS: Map<u32, Plot> = Map::new("plots");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlotEntry {
+    pub token_id: u32,
+    pub price: Coin,
+    pub metadata: Metadata,
+}
+
+/// Order paid off-chain, delivered from the market supply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FiatOrder {
+    pub recipient: Addr,
+    pub quantity: u32,
+    /// minted, or taken from the inventory in inventory mode
+    pub token_ids: Vec<TokenID>,
+    pub fulfiller: Addr,
+    pub fulfilled: u64,
+}
+
+/// Fulfilled fiat orders, by web shop order id
+pub const FIAT_ORDERS: Map<&str, FiatOrder> = Map::new("fiat_orders");
+
+/// Waitlist entry, fulfilled when the market supply is increased
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WaitlistEntry {
+    pub buyer: Addr,
+    pub quantity: u32,
+    /// escrowed until fulfilled or withdrawn
+    pub deposit: Coin,
+    pub joined: u64,
+}
+
+/// Waitlist, by entry id, in FIFO order
+pub const WAITLIST: Map<u64, WaitlistEntry> = Map::new("waitlist");
+pub const WAITLIST_COUNT: Item<u64> = Item::new("waitlist_count");
+
+/// cw721 contracts gating the pre-sale
+///
+/// Buyers without whitelist entry get one pre-sale slot per token they hold on these contracts.
+pub const GATING_CONTRACTS: Item<Vec<Addr>> = Item::new("gating_contracts");
+
+/// Gating tokens already used, by contract and token id, with the buyer who used them
+pub const USED_GATING_TOKENS: Map<(Addr, String), Addr> = Map::new("used_gating_tokens");
+
+/// Holder loyalty tier
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LoyaltyTier {
+    /// tokens held on the loyalty contracts, all contracts together
+    pub min_tokens: u32,
+    /// share of the sell price taken off
+    pub discount: Decimal,
+}
+
+/// Holder loyalty discounts, the best tier reached applies
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LoyaltyConfig {
+    pub contracts: Vec<Addr>,
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+pub const LOYALTY: Item<LoyaltyConfig> = Item::new("loyalty");
+
+/// Loyalty tokens which gave a discount, by contract and token id, with the buyer who used them
+///
+/// A token only counts for the buyer who first used it.
+pub const USED_LOYALTY_TOKENS: Map<(Addr, String), Addr> = Map::new("used_loyalty_tokens");
+
+/// Price oracle converting prices in a reference currency to the payment denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Oracle {
+    pub contract: Addr,
+    /// second price source the rate is checked against
+    pub check_contract: Addr,
+    /// denom of the prices to convert, e.g. "uusd"
+    pub reference_denom: String,
+    pub payment_denom: String,
+    /// maximum age of the oracle rate, in seconds
+    pub max_staleness: u64,
+    /// maximum difference between the two sources, relative to the check rate
+    pub max_deviation: Decimal,
+}
+
+pub const ORACLE: Item<Option<Oracle>> = Item::new("oracle");
+
+/// Funds escrowed for referrers, bidders, lottery entrants and the waitlist, by denom
+pub const ESCROWED: Map<&str, Uint128> = Map::new("escrowed");
@@ -1,5 +1,30 @@
 mod admin_crud;
+mod auctions;
+mod collections;
+mod fiat_orders;
+mod gating;
+mod generated_attributes;
+mod gift;
+mod inventory;
+mod lottery;
+mod loyalty;
+mod metadata_templates;
+mod move_supply;
+mod oracle;
+mod permit;
+mod plots;
 mod presale;
 mod price;
+mod products;
+mod referral;
+mod reveal;
+mod rounds;
+mod sale_stats;
+mod sealed_auctions;
 mod supply;
+mod timelock;
+mod treasury;
 mod update_metadata;
+mod voucher;
+mod waitlist;
+mod withdraw_approval;
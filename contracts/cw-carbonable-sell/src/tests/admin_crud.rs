@@ -98,7 +98,11 @@ mod cw_carbonable {
             },
         );
 
-        assert!(res.is_ok());
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
     }
 
     #[test]
@@ -107,7 +111,7 @@ mod cw_carbonable {
 
         helper_instantiate(&mut deps);
 
-        let info = mock_info("admin1_addr", &[]);
+        let info = mock_info("owner_addr", &[]);
         let res = execute(
             deps.as_mut(),
             mock_env(),
@@ -184,7 +188,11 @@ mod cw_carbonable {
             },
         );
 
-        assert!(res.is_ok());
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
     }
 
     #[test]
@@ -193,7 +201,7 @@ mod cw_carbonable {
 
         helper_instantiate(&mut deps);
 
-        let info = mock_info("admin1_addr", &[]);
+        let info = mock_info("owner_addr", &[]);
         let res = execute(
             deps.as_mut(),
             mock_env(),
@@ -0,0 +1,303 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{AuctionResponse, ExecuteMsg, QueryMsg};
+    use crate::state::{AuctionConfig, Bid, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_json, BankMsg, CosmosMsg, Env, OwnedDeps, Response, Uint128,
+    };
+    use cw_carbonable_lib::Metadata;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let now = mock_env().block.time.seconds();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateAuction {
+                config: AuctionConfig {
+                    metadata: Metadata {
+                        name: Some(String::from("Flagship plot")),
+                        ..Default::default()
+                    },
+                    reserve_price: coin(100, String::from("juno")),
+                    min_increment: Uint128::new(10),
+                    start: now,
+                    end: now + 3600,
+                    extension: 300,
+                },
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_bid(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        bidder: &str,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(bidder, &[coin(amount, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::PlaceBid { auction_id: 1 },
+        )
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    #[test]
+    fn create_auction_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let now = mock_env().block.time.seconds();
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateAuction {
+                config: AuctionConfig {
+                    metadata: Metadata::default(),
+                    reserve_price: coin(1, String::from("juno")),
+                    min_increment: Uint128::new(1),
+                    start: now,
+                    end: now + 60,
+                    extension: 0,
+                },
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn bid_and_refund_outbid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_bid(&mut deps, mock_env(), "alice", 90);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::BidTooLow { min_bid } => {
+                assert_eq!(coin(100, String::from("juno")), min_bid)
+            }
+            _ => unreachable!(),
+        }
+
+        let res = helper_bid(&mut deps, mock_env(), "alice", 100).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // the increment applies on top of the highest bid
+        let res = helper_bid(&mut deps, mock_env(), "bob", 105);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::BidTooLow { min_bid } => {
+                assert_eq!(coin(110, String::from("juno")), min_bid)
+            }
+            _ => unreachable!(),
+        }
+
+        let res = helper_bid(&mut deps, mock_env(), "bob", 110).unwrap();
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: vec![coin(100, String::from("juno"))],
+            }),
+            res.messages[0].msg
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AuctionBids {
+                auction_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let bids: Vec<Bid> = from_json(&res).unwrap();
+        assert_eq!(2, bids.len());
+        assert_eq!("bob", bids[1].bidder);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AuctionBids {
+                auction_id: 1,
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+        let bids: Vec<Bid> = from_json(&res).unwrap();
+        assert_eq!(1, bids.len());
+        assert_eq!("bob", bids[0].bidder);
+    }
+
+    #[test]
+    fn bid_invalid_funds() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        for funds in [
+            vec![],
+            vec![coin(100, String::from("ujuno"))],
+            vec![
+                coin(100, String::from("juno")),
+                coin(100, String::from("ujuno")),
+            ],
+        ] {
+            let info = mock_info("alice", &funds);
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                ExecuteMsg::PlaceBid { auction_id: 1 },
+            );
+            assert!(res.is_err());
+            match res.err().unwrap() {
+                ContractError::InvalidBidFunds {} => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn create_auction_zero_increment() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let now = mock_env().block.time.seconds();
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateAuction {
+                config: AuctionConfig {
+                    metadata: Metadata::default(),
+                    reserve_price: coin(1, String::from("juno")),
+                    min_increment: Uint128::zero(),
+                    start: now,
+                    end: now + 60,
+                    extension: 0,
+                },
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidAuction {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn late_bid_extends_auction() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_bid(&mut deps, env_at(3500), "alice", 100).unwrap();
+        let end = mock_env().block.time.seconds() + 3800;
+        assert!(res.attributes.contains(&attr("end", end.to_string())));
+
+        // the original end is pushed back
+        let res = helper_bid(&mut deps, env_at(3700), "bob", 110);
+        assert!(res.is_ok());
+
+        let res = helper_bid(&mut deps, env_at(4000), "alice", 120);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AuctionEnded {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn settle_auction() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_bid(&mut deps, mock_env(), "alice", 150);
+        assert!(res.is_ok());
+
+        let info = mock_info("anyone", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SettleAuction { auction_id: 1 },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AuctionNotEnded {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(3600),
+            info,
+            ExecuteMsg::SettleAuction { auction_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(1, res.messages.len());
+        assert!(res.attributes.contains(&attr("winner", "alice")));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(1, state.total_market_minted);
+        assert_eq!(1, state.last_token_id);
+
+        // settled auctions are not active anymore
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auctions {}).unwrap();
+        let auctions: Vec<AuctionResponse> = from_json(&res).unwrap();
+        assert!(auctions.is_empty());
+    }
+}
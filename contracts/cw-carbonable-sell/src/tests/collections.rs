@@ -0,0 +1,339 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{CollectionConfig, CollectionResponse, ExecuteMsg, QueryMsg};
+    use crate::state::{RoundConfig, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, CosmosMsg, OwnedDeps, Response, WasmMsg};
+    use cw_carbonable_lib::Metadata;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        for (collection_id, project, price) in [(1, "banegas", 10), (2, "las-delicias", 20)] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::SetCollection {
+                    collection_id,
+                    config: CollectionConfig {
+                        name: String::from(project),
+                        nft_contract: format!("{}_nft", project),
+                        price: coin(price, String::from("juno")),
+                        supply: 3,
+                        metadata: Metadata {
+                            name: Some(String::from(project)),
+                            ..Default::default()
+                        },
+                    },
+                },
+            );
+            assert!(res.is_ok());
+        }
+    }
+
+    fn helper_buy_collection(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        collection_id: u32,
+        quantity: u32,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("test", &[coin(amount, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: Some(collection_id),
+                max_price: None,
+            },
+        )
+    }
+
+    fn minted(msg: &CosmosMsg) -> (String, String) {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                cw_carbonable_lib::ExecuteMsg::Mint(mint) => (contract_addr.clone(), mint.token_id),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn set_collection_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetCollection {
+                collection_id: 3,
+                config: CollectionConfig {
+                    name: String::from("fake"),
+                    nft_contract: String::from("fake_nft"),
+                    price: coin(1, String::from("juno")),
+                    supply: 100,
+                    metadata: Metadata::default(),
+                },
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn buy_from_collections() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // collection price, not the sell price
+        let res = helper_buy_collection(&mut deps, 2, 2, 20);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy_collection(&mut deps, 1, 2, 20).unwrap();
+        assert_eq!(
+            (String::from("banegas_nft"), String::from("1")),
+            minted(&res.messages[0].msg)
+        );
+        assert_eq!(
+            (String::from("banegas_nft"), String::from("2")),
+            minted(&res.messages[1].msg)
+        );
+
+        // token ids are numbered per collection
+        let res = helper_buy_collection(&mut deps, 2, 1, 20).unwrap();
+        assert_eq!(
+            (String::from("las-delicias_nft"), String::from("1")),
+            minted(&res.messages[0].msg)
+        );
+
+        // the market supply is left untouched
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(0, state.total_market_minted);
+        assert_eq!(0, state.last_token_id);
+    }
+
+    #[test]
+    fn collection_sold_out() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy_collection(&mut deps, 1, 3, 30);
+        assert!(res.is_ok());
+
+        let res = helper_buy_collection(&mut deps, 1, 1, 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy_collection(&mut deps, 4, 1, 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::CollectionNotFound { collection_id } => assert_eq!(4, collection_id),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn query_collections() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy_collection(&mut deps, 2, 2, 40);
+        assert!(res.is_ok());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Collection { collection_id: 2 },
+        )
+        .unwrap();
+        let collection: CollectionResponse = from_json(&res).unwrap();
+        assert_eq!(2, collection.collection.minted);
+        assert_eq!(1, collection.remaining);
+        assert_eq!(
+            vec![coin(40, String::from("juno"))],
+            collection.collection.revenue
+        );
+
+        // the NFT contract is locked once minted
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetCollection {
+                collection_id: 2,
+                config: CollectionConfig {
+                    name: String::from("las-delicias"),
+                    nft_contract: String::from("other_nft"),
+                    price: coin(20, String::from("juno")),
+                    supply: 3,
+                    metadata: Metadata::default(),
+                },
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::CollectionNftContractLocked {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Collections {}).unwrap();
+        let collections: Vec<CollectionResponse> = from_json(&res).unwrap();
+        assert_eq!(2, collections.len());
+        assert_eq!(3, collections[0].remaining);
+        assert_eq!("las-delicias_nft", collections[1].collection.nft_contract);
+    }
+
+    #[test]
+    fn collection_nft_contract_in_use() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateNftContract {
+                address: String::from("main_nft"),
+            },
+        );
+        assert!(res.is_ok());
+
+        for nft_contract in ["main_nft", "banegas_nft"] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::SetCollection {
+                    collection_id: 3,
+                    config: CollectionConfig {
+                        name: String::from("copy"),
+                        nft_contract: String::from(nft_contract),
+                        price: coin(1, String::from("juno")),
+                        supply: 3,
+                        metadata: Metadata::default(),
+                    },
+                },
+            );
+
+            assert!(res.is_err());
+            match res.err().unwrap() {
+                ContractError::NftContractInUse { address } => assert_eq!(nft_contract, address),
+                _ => unreachable!(),
+            }
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateNftContract {
+                address: String::from("las-delicias_nft"),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NftContractInUse { .. } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn collection_buy_during_round() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetRound {
+                round_id: 1,
+                config: RoundConfig {
+                    name: String::from("public"),
+                    price: coin(1, String::from("juno")),
+                    supply: 5,
+                    start: mock_env().block.time.seconds(),
+                    end: mock_env().block.time.seconds() + 100,
+                    whitelist_only: false,
+                    max_per_wallet: None,
+                    carry_forward: false,
+                },
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ActivateRound { round_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = helper_buy_collection(&mut deps, 1, 1, 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RoundOpen { round_id: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::state::{FiatOrder, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, from_json, Addr, OwnedDeps, Response};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 5,
+                market_supply: 3,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddFulfiller {
+                address: String::from("webshop"),
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_fulfill(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        sender: &str,
+        order_id: &str,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(sender, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::FulfillFiatOrder {
+                order_id: String::from(order_id),
+                recipient: String::from("company"),
+                quantity,
+            },
+        )
+    }
+
+    #[test]
+    fn fulfill_fiat_order_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_fulfill(&mut deps, "owner_addr", "order-1", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fulfill_fiat_order_once() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", 2).unwrap();
+        assert_eq!(2, res.messages.len());
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", 2);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::FiatOrderAlreadyFulfilled { order_id } => {
+                assert_eq!("order-1", order_id)
+            }
+            _ => unreachable!(),
+        }
+
+        // delivered from the market supply
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(2, state.total_market_minted);
+        assert_eq!(0, state.total_reserved_minted);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FiatOrder {
+                order_id: String::from("order-1"),
+            },
+        )
+        .unwrap();
+        let order: Option<FiatOrder> = from_json(&res).unwrap();
+        let order = order.unwrap();
+        assert_eq!(Addr::unchecked("company"), order.recipient);
+        assert_eq!(vec!["1", "2"], order.token_ids);
+        assert_eq!(Addr::unchecked("webshop"), order.fulfiller);
+    }
+
+    #[test]
+    fn fulfill_fiat_order_market_supply() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", 4);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fulfill_fiat_order_quantity() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", 0);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidQuantity {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", u32::MAX);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::MultiBuyQuantityTooHigh {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fulfiller_crud() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::AddFulfiller {
+                address: String::from("webshop"),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AddressAlreadyRegistered { address } => assert_eq!("webshop", address),
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::AddFulfiller {
+                address: String::from("backoffice"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Fulfillers {}).unwrap();
+        let fulfillers: Vec<Addr> = from_json(&res).unwrap();
+        assert_eq!(
+            vec![Addr::unchecked("backoffice"), Addr::unchecked("webshop")],
+            fulfillers
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RemoveFulfiller {
+                address: String::from("webshop"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = helper_fulfill(&mut deps, "webshop", "order-1", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coin, coins, from_json, to_json_binary, Addr, ContractResult, OwnedDeps, Response,
+        SystemError, SystemResult, WasmQuery,
+    };
+    use cw721::{Cw721QueryMsg, TokensResponse};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: true,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateGatingContracts {
+                contracts: vec![String::from("genesis_nft")],
+            },
+        );
+        assert!(res.is_ok());
+
+        // carol holds token 3, after bob
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "genesis_nft" => {
+                let tokens: Vec<&str> = match from_json(msg).unwrap() {
+                    Cw721QueryMsg::Tokens { owner, .. } => match owner.as_str() {
+                        "alice" => vec!["1", "2"],
+                        "bob" | "carol" => vec!["3"],
+                        _ => vec![],
+                    },
+                    _ => unreachable!(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&TokensResponse {
+                        tokens: tokens.into_iter().map(String::from).collect(),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+    }
+
+    fn helper_buy(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        buyer: &str,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(buyer, &[coin(0, String::from("ujuno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+    }
+
+    fn query_usage(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        token_id: &str,
+    ) -> Option<Addr> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GatingTokenUsage {
+                contract: String::from("genesis_nft"),
+                token_id: String::from(token_id),
+            },
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    }
+
+    #[test]
+    fn update_gating_contracts_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("alice", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateGatingContracts { contracts: vec![] },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn gated_presale_slot_per_token() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy(&mut deps, "alice", 2);
+        assert!(res.is_ok());
+        assert_eq!(Some(Addr::unchecked("alice")), query_usage(&deps, "1"));
+        assert_eq!(Some(Addr::unchecked("alice")), query_usage(&deps, "2"));
+        assert_eq!(None, query_usage(&deps, "3"));
+
+        let res = helper_buy(&mut deps, "alice", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoSlotAvailableLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn gated_presale_non_holder() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy(&mut deps, "dave", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AddressNotWhitelisted {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn gating_token_used_once() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy(&mut deps, "bob", 1);
+        assert!(res.is_ok());
+
+        // the token changed hands, it can't open the pre-sale again
+        let res = helper_buy(&mut deps, "carol", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoSlotAvailableLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GatingContracts {}).unwrap();
+        let contracts: Vec<Addr> = from_json(&res).unwrap();
+        assert_eq!(vec![Addr::unchecked("genesis_nft")], contracts);
+    }
+}
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::state::GeneratedAttribute;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, CosmosMsg, OwnedDeps, Response, WasmMsg};
+    use cw_carbonable_lib::{Metadata, Trait};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 2,
+                market_supply: 8,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(4, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateMetadata {
+                metadata: Metadata {
+                    name: Some(String::from("Carbonable")),
+                    attributes: Some(vec![Trait {
+                        display_type: None,
+                        trait_type: String::from("project"),
+                        value: String::from("Banegas Farm"),
+                    }]),
+                    ..Default::default()
+                },
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn minted_attributes(res: &Response, index: usize) -> Vec<Trait> {
+        match &res.messages[index].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                    cw_carbonable_lib::ExecuteMsg::Mint(mint) => {
+                        mint.extension.unwrap().attributes.unwrap()
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn update_generated_attributes_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateGeneratedAttributes {
+                attributes: vec![GeneratedAttribute::SerialNumber],
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn no_generated_attributes() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GeneratedAttributes {}).unwrap();
+        let attributes: Vec<GeneratedAttribute> = from_json(&res).unwrap();
+        assert!(attributes.is_empty());
+
+        let info = mock_info("test", &[coin(4, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        let attributes = minted_attributes(&res, 0);
+        assert_eq!(1, attributes.len());
+        assert_eq!("project", attributes[0].trait_type);
+    }
+
+    #[test]
+    fn generated_attributes_on_buy_and_airdrop() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateGeneratedAttributes {
+                attributes: vec![
+                    GeneratedAttribute::SerialNumber,
+                    GeneratedAttribute::Edition,
+                    GeneratedAttribute::MintPhase,
+                    GeneratedAttribute::MintDate,
+                    GeneratedAttribute::BuyerType,
+                ],
+            },
+        );
+        assert!(res.is_ok());
+
+        let info = mock_info("test", &[coin(8, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        let attributes = minted_attributes(&res, 1);
+        let values: Vec<(String, String)> = attributes
+            .into_iter()
+            .map(|t| (t.trait_type, t.value))
+            .collect();
+        assert_eq!(
+            vec![
+                (String::from("project"), String::from("Banegas Farm")),
+                (String::from("serial_number"), String::from("2")),
+                (String::from("edition"), String::from("2 of 10")),
+                (String::from("mint_phase"), String::from("public_sale")),
+                (
+                    String::from("mint_date"),
+                    mock_env().block.time.seconds().to_string()
+                ),
+                (String::from("buyer_type"), String::from("market")),
+            ],
+            values
+        );
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Airdrop {
+                receivers: vec![String::from("receiver")],
+            },
+        )
+        .unwrap();
+
+        let attributes = minted_attributes(&res, 0);
+        assert_eq!("3", attributes[1].value);
+        assert_eq!("airdrop", attributes[3].value);
+        assert_eq!("reserved", attributes[5].value);
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, WhiteListEntry};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{attr, coin, coins, from_json, CosmosMsg, OwnedDeps, WasmMsg};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: true,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddToWhitelist {
+                entries: vec![WhiteListEntry {
+                    address: String::from("company"),
+                    nb_slots: 2,
+                }],
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn minted_owner(msg: &CosmosMsg) -> String {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                    cw_carbonable_lib::ExecuteMsg::Mint(mint) => mint.owner,
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn gift_whitelist_applies_to_payer() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // the recipient being whitelisted is not enough
+        let info = mock_info("employee", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: Some(String::from("company")),
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AddressNotWhitelisted {} => {}
+            _ => unreachable!(),
+        }
+
+        let info = mock_info("company", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: Some(String::from("employee")),
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!("employee", minted_owner(&res.messages[0].msg));
+        assert!(res.attributes.contains(&attr("payer", "company")));
+        assert!(res.attributes.contains(&attr("recipient", "employee")));
+
+        // payer slots are burnt
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoSlotAvailableLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn gift_multi_buy_recipients() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("company", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: Some(vec![String::from("alice"), String::from("bob")]),
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(2, res.messages.len());
+        assert_eq!("alice", minted_owner(&res.messages[0].msg));
+        assert_eq!("bob", minted_owner(&res.messages[1].msg));
+    }
+
+    #[test]
+    fn gift_recipients_count_mismatch() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("company", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: Some(vec![String::from("alice")]),
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidRecipients {} => {}
+            _ => unreachable!(),
+        }
+    }
+}
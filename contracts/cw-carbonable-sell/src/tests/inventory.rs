@@ -0,0 +1,259 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, InventoryResponse, QueryMsg};
+    use crate::state::InventoryOrder;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_json, to_json_binary, Binary, CosmosMsg, OwnedDeps, Response,
+        WasmMsg,
+    };
+    use cw721::Cw721ReceiveMsg;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateNftContract {
+                address: String::from("nft_contract"),
+            },
+        );
+        assert!(res.is_ok());
+
+        for token_id in ["a", "b", "c"] {
+            let res = helper_receive_nft(deps, "nft_contract", "owner_addr", token_id);
+            assert!(res.is_ok());
+        }
+    }
+
+    fn helper_receive_nft(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        contract: &str,
+        sender: &str,
+        token_id: &str,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(contract, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: String::from(sender),
+                token_id: String::from(token_id),
+                msg: Binary::default(),
+            }),
+        )
+    }
+
+    fn helper_inventory_mode(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        mode: InventoryOrder,
+    ) {
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateInventoryMode { mode: Some(mode) },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_buy(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+    }
+
+    fn transfer(token_id: &str, recipient: &str) -> CosmosMsg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("nft_contract"),
+            msg: to_json_binary(&cw_carbonable_lib::ExecuteMsg::TransferNft {
+                recipient: String::from(recipient),
+                token_id: String::from(token_id),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    }
+
+    fn query_inventory(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Vec<String> {
+        query_inventory_page(deps, None, None)
+    }
+
+    fn query_inventory_page(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<String> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Inventory {
+                start_after: start_after.map(String::from),
+                limit,
+            },
+        )
+        .unwrap();
+        let inventory: InventoryResponse = from_json(&res).unwrap();
+        inventory.token_ids
+    }
+
+    #[test]
+    fn receive_nft_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_receive_nft(&mut deps, "other_nft", "owner_addr", "d");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_receive_nft(&mut deps, "nft_contract", "test", "d");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        assert_eq!(vec!["a", "b", "c"], query_inventory(&deps));
+    }
+
+    #[test]
+    fn buy_from_inventory_in_order() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_inventory_mode(&mut deps, InventoryOrder::Sequential);
+
+        let res = helper_buy(&mut deps, 2).unwrap();
+        assert_eq!(transfer("a", "test"), res.messages[0].msg);
+        assert_eq!(transfer("b", "test"), res.messages[1].msg);
+        assert_eq!(vec!["c"], query_inventory(&deps));
+
+        let res = helper_buy(&mut deps, 2);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn buy_from_inventory_at_random() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_inventory_mode(&mut deps, InventoryOrder::Random);
+
+        let res = helper_buy(&mut deps, 1).unwrap();
+        let token_id = res
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "token_id")
+            .unwrap()
+            .value
+            .clone();
+        assert_eq!(transfer(&token_id, "test"), res.messages[0].msg);
+
+        let inventory = query_inventory(&deps);
+        assert_eq!(2, inventory.len());
+        assert!(!inventory.contains(&token_id));
+
+        let res = helper_buy(&mut deps, 2).unwrap();
+        assert_eq!(2, res.messages.len());
+        assert!(query_inventory(&deps).is_empty());
+
+        let res = helper_buy(&mut deps, 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn query_inventory_pages() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        assert_eq!(vec!["a", "b"], query_inventory_page(&deps, None, Some(2)));
+        assert_eq!(vec!["b"], query_inventory_page(&deps, Some("a"), Some(1)));
+        assert_eq!(vec!["c"], query_inventory_page(&deps, Some("b"), None));
+    }
+
+    #[test]
+    fn withdraw_inventory() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::WithdrawInventory {
+                token_ids: vec![String::from("d")],
+                recipient: String::from("owner_addr"),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InventoryTokenNotFound { token_id } => assert_eq!("d", token_id),
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::WithdrawInventory {
+                token_ids: vec![String::from("b")],
+                recipient: String::from("owner_addr"),
+            },
+        )
+        .unwrap();
+        assert_eq!(transfer("b", "owner_addr"), res.messages[0].msg);
+        assert!(res
+            .attributes
+            .contains(&attr("method", "try_withdraw_inventory")));
+        assert_eq!(vec!["a", "c"], query_inventory(&deps));
+    }
+}
@@ -0,0 +1,426 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, LotteryResponse, QueryMsg, WhiteListEntry};
+    use crate::state::{LotteryConfig, LotteryEntry, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_json, BankMsg, CosmosMsg, Env, OwnedDeps, Response,
+    };
+    use sha2::{Digest, Sha256};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: true,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::AddToWhitelist {
+                entries: ["alice", "bob", "carol"]
+                    .iter()
+                    .map(|address| WhiteListEntry {
+                        address: String::from(*address),
+                        nb_slots: 3,
+                    })
+                    .collect(),
+            },
+        );
+        assert!(res.is_ok());
+
+        let now = mock_env().block.time.seconds();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateLottery {
+                config: LotteryConfig {
+                    price: coin(10, String::from("juno")),
+                    supply: 4,
+                    registration_start: now,
+                    registration_end: now + 3600,
+                    seed_hash: hex::encode(Sha256::digest(b"secret")),
+                    draw_deadline: now + 7200,
+                    claim_deadline: now + 10800,
+                },
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_register(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        address: &str,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(
+            address,
+            &[coin(10 * quantity as u128, String::from("juno"))],
+        );
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::RegisterLottery { quantity },
+        )
+    }
+
+    fn helper_draw(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        seed: &str,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("owner_addr", &[]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::DrawLottery {
+                seed: String::from(seed),
+            },
+        )
+    }
+
+    fn helper_claim(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+    ) -> Result<Response, ContractError> {
+        helper_claim_at(deps, after_registration(), address)
+    }
+
+    fn helper_claim_at(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        address: &str,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(address, &[]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ClaimLottery { lottery_id: 1 },
+        )
+    }
+
+    fn query_entry(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+    ) -> LotteryEntry {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LotteryEntry {
+                lottery_id: 1,
+                address: String::from(address),
+            },
+        )
+        .unwrap();
+        let entry: Option<LotteryEntry> = from_json(&res).unwrap();
+        entry.unwrap()
+    }
+
+    fn after_registration() -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3600);
+        env
+    }
+
+    fn after_deadline() -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(7200);
+        env
+    }
+
+    #[test]
+    fn register_lottery() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_register(&mut deps, mock_env(), "dave", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AddressNotWhitelisted {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 0);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidQuantity {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 4);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoSlotAvailableLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_register(&mut deps, after_registration(), "alice", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RegistrationNotOpen {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 2);
+        assert!(res.is_ok());
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotteryAlreadyRegistered {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn draw_lottery_seed() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_draw(&mut deps, mock_env(), "secret");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RegistrationNotEnded {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_draw(&mut deps, after_registration(), "guess");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotterySeedMismatch {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_claim(&mut deps, "alice");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotteryNotDrawn {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn oversubscribed_lottery() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        for (address, quantity) in [("alice", 3), ("bob", 3), ("carol", 2)] {
+            let res = helper_register(&mut deps, mock_env(), address, quantity);
+            assert!(res.is_ok());
+        }
+
+        let res = helper_draw(&mut deps, after_registration(), "secret").unwrap();
+        assert!(res.attributes.contains(&attr("nb_winning_tickets", "4")));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Lottery {}).unwrap();
+        let lottery: Option<LotteryResponse> = from_json(&res).unwrap();
+        let lottery = lottery.unwrap().lottery;
+        assert_eq!(3, lottery.nb_entrants);
+        assert_eq!(8, lottery.nb_tickets);
+        assert_eq!(Some(String::from("secret")), lottery.seed);
+
+        let mut nb_won = 0;
+        for (address, quantity) in [("alice", 3), ("bob", 3), ("carol", 2)] {
+            let won = query_entry(&deps, address).won;
+            nb_won += won;
+
+            // winners get their tokens, the other tickets are refunded
+            let res = helper_claim(&mut deps, address).unwrap();
+            let mints = res
+                .messages
+                .iter()
+                .filter(|msg| matches!(msg.msg, CosmosMsg::Wasm(_)))
+                .count();
+            assert_eq!(won as usize, mints);
+            if won < quantity {
+                assert_eq!(
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: String::from(address),
+                        amount: vec![coin(10 * (quantity - won) as u128, String::from("juno"))],
+                    }),
+                    res.messages.last().unwrap().msg
+                );
+            }
+        }
+        assert_eq!(4, nb_won);
+    }
+
+    #[test]
+    fn undersubscribed_lottery() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 2);
+        assert!(res.is_ok());
+
+        let res = helper_draw(&mut deps, after_registration(), "secret");
+        assert!(res.is_ok());
+        assert_eq!(2, query_entry(&deps, "alice").won);
+
+        // tokens not won go back to the market supply
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(2, state.total_market_minted);
+
+        let res = helper_claim(&mut deps, "alice").unwrap();
+        assert_eq!(2, res.messages.len());
+
+        let res = helper_claim(&mut deps, "alice");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotteryEntryNotFound {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn expired_lottery() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 2);
+        assert!(res.is_ok());
+
+        let res = helper_draw(&mut deps, after_deadline(), "secret");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotteryDrawExpired {} => {}
+            _ => unreachable!(),
+        }
+
+        // no draw, the whole deposit is refunded
+        let res = helper_claim_at(&mut deps, after_deadline(), "alice").unwrap();
+        assert_eq!(
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: vec![coin(20, String::from("juno"))],
+            })],
+            res.messages
+                .into_iter()
+                .map(|msg| msg.msg)
+                .collect::<Vec<_>>()
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(0, state.total_market_minted);
+    }
+
+    #[test]
+    fn new_lottery_once_claimed() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 2);
+        assert!(res.is_ok());
+        let res = helper_draw(&mut deps, after_registration(), "secret");
+        assert!(res.is_ok());
+
+        let now = after_registration().block.time.seconds();
+        let msg = ExecuteMsg::CreateLottery {
+            config: LotteryConfig {
+                price: coin(10, String::from("juno")),
+                supply: 2,
+                registration_start: now,
+                registration_end: now + 3600,
+                seed_hash: hex::encode(Sha256::digest(b"another secret")),
+                draw_deadline: now + 7200,
+                claim_deadline: now + 10800,
+            },
+        };
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            after_registration(),
+            info.clone(),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::LotteryAlreadyCreated {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_claim(&mut deps, "alice");
+        assert!(res.is_ok());
+
+        let res = execute(deps.as_mut(), after_registration(), info, msg);
+        assert!(res.is_ok());
+
+        let res = helper_register(&mut deps, after_registration(), "alice", 1);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn new_lottery_after_claim_deadline() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_register(&mut deps, mock_env(), "alice", 2);
+        assert!(res.is_ok());
+        let res = helper_draw(&mut deps, after_registration(), "secret");
+        assert!(res.is_ok());
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(10800);
+        let now = env.block.time.seconds();
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::CreateLottery {
+                config: LotteryConfig {
+                    price: coin(10, String::from("juno")),
+                    supply: 2,
+                    registration_start: now,
+                    registration_end: now + 3600,
+                    seed_hash: hex::encode(Sha256::digest(b"another secret")),
+                    draw_deadline: now + 7200,
+                    claim_deadline: now + 10800,
+                },
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("lottery_id", "2")));
+
+        // the unclaimed entry of the previous lottery is still claimable
+        let res = helper_claim_at(&mut deps, env, "alice").unwrap();
+        assert!(res.attributes.contains(&attr("won", "2")));
+    }
+}
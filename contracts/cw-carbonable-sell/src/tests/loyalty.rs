@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, PriceForResponse, QueryMsg};
+    use crate::state::LoyaltyTier;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coin, coins, from_json, to_json_binary, Addr, ContractResult, Decimal, OwnedDeps, Response,
+        SystemError, SystemResult, WasmQuery,
+    };
+    use cw721::{Cw721QueryMsg, TokensResponse};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(100, String::from("ujuno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateLoyalty {
+                contracts: vec![String::from("genesis_nft"), String::from("forest_nft")],
+                tiers: vec![
+                    LoyaltyTier {
+                        min_tokens: 1,
+                        discount: Decimal::percent(10),
+                    },
+                    LoyaltyTier {
+                        min_tokens: 3,
+                        discount: Decimal::percent(25),
+                    },
+                ],
+            },
+        );
+        assert!(res.is_ok());
+
+        // alice holds 2 tokens on each contract, bob a single one, dave holds the first tokens
+        // of alice too
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let tokens: Vec<&str> = match from_json(msg).unwrap() {
+                    Cw721QueryMsg::Tokens { owner, .. } => {
+                        match (contract_addr.as_str(), owner.as_str()) {
+                            ("genesis_nft", "alice") => vec!["1", "2"],
+                            ("forest_nft", "alice") => vec!["7", "8"],
+                            ("forest_nft", "bob") => vec!["9"],
+                            ("genesis_nft", "dave") => vec!["1", "2"],
+                            _ => vec![],
+                        }
+                    }
+                    _ => unreachable!(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&TokensResponse {
+                        tokens: tokens.into_iter().map(String::from).collect(),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+    }
+
+    fn helper_buy(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        buyer: &str,
+        amount: u128,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(buyer, &[coin(amount, String::from("ujuno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+    }
+
+    fn query_price_for(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+        quantity: u32,
+    ) -> PriceForResponse {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PriceFor {
+                address: String::from(address),
+                quantity,
+            },
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    }
+
+    #[test]
+    fn update_loyalty_invalid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::UpdateLoyalty {
+                contracts: vec![],
+                tiers: vec![],
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner_addr", &[]),
+            ExecuteMsg::UpdateLoyalty {
+                contracts: vec![String::from("genesis_nft")],
+                tiers: vec![LoyaltyTier {
+                    min_tokens: 1,
+                    discount: Decimal::percent(120),
+                }],
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidLoyaltyTier {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn price_for_tiers() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // tokens are counted across contracts, until the highest tier is reached
+        let res = query_price_for(&deps, "alice", 2);
+        assert_eq!(3, res.nb_held);
+        assert_eq!(Decimal::percent(25), res.discount);
+        assert_eq!(coin(75, "ujuno"), res.unit_price);
+        assert_eq!(coin(150, "ujuno"), res.price);
+
+        let res = query_price_for(&deps, "bob", 2);
+        assert_eq!(1, res.nb_held);
+        assert_eq!(coin(180, "ujuno"), res.price);
+
+        let res = query_price_for(&deps, "carol", 2);
+        assert_eq!(0, res.nb_held);
+        assert_eq!(Decimal::zero(), res.discount);
+        assert_eq!(coin(200, "ujuno"), res.price);
+    }
+
+    #[test]
+    fn buy_with_loyalty_discount() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy(&mut deps, "bob", 179, 2);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy(&mut deps, "bob", 180, 2);
+        assert!(res.is_ok());
+
+        let res = helper_buy(&mut deps, "alice", 150, 2);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn loyalty_tokens_used_once() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        assert_eq!(2, query_price_for(&deps, "dave", 1).nb_held);
+
+        let res = helper_buy(&mut deps, "alice", 75, 1);
+        assert!(res.is_ok());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LoyaltyTokenUsage {
+                contract: String::from("genesis_nft"),
+                token_id: String::from("1"),
+            },
+        )
+        .unwrap();
+        let buyer: Option<Addr> = from_json(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("alice")), buyer);
+
+        // the tokens keep counting for alice, not for another wallet
+        let res = helper_buy(&mut deps, "alice", 75, 1);
+        assert!(res.is_ok());
+
+        let res = query_price_for(&deps, "dave", 1);
+        assert_eq!(0, res.nb_held);
+        assert_eq!(coin(100, "ujuno"), res.price);
+
+        let res = helper_buy(&mut deps, "dave", 90, 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn no_loyalty_tiers() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner_addr", &[]),
+            ExecuteMsg::UpdateLoyalty {
+                contracts: vec![String::from("genesis_nft")],
+                tiers: vec![],
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query_price_for(&deps, "alice", 1);
+        assert_eq!(0, res.nb_held);
+        assert_eq!(coin(100, "ujuno"), res.price);
+
+        let res = helper_buy(&mut deps, "alice", 75, 1);
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, MetadataTemplateResponse, QueryMsg};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, CosmosMsg, OwnedDeps, Response, WasmMsg};
+    use cw_carbonable_lib::Metadata;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        for (template_id, name, weight) in [(1, "Oak", 1), (2, "Pine", 3)] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::SetMetadataTemplate {
+                    template_id,
+                    metadata: Metadata {
+                        name: Some(String::from(name)),
+                        ..Default::default()
+                    },
+                    weight,
+                    supply: 1,
+                },
+            );
+            assert!(res.is_ok());
+        }
+    }
+
+    fn minted_names(res: &Response) -> Vec<String> {
+        res.messages
+            .iter()
+            .map(|message| match &message.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                    match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                        cw_carbonable_lib::ExecuteMsg::Mint(mint) => {
+                            mint.extension.unwrap().name.unwrap()
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn set_metadata_template_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetMetadataTemplate {
+                template_id: 3,
+                metadata: Metadata::default(),
+                weight: 1,
+                supply: 1,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn set_metadata_template_without_weight() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetMetadataTemplate {
+                template_id: 3,
+                metadata: Metadata::default(),
+                weight: 0,
+                supply: 1,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidTemplateWeight {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn templates_exhausted() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        // each template has a single slot: both are used
+        let mut names = minted_names(&res);
+        names.sort();
+        assert_eq!(vec![String::from("Oak"), String::from("Pine")], names);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MetadataTemplates {}).unwrap();
+        let templates: Vec<MetadataTemplateResponse> = from_json(&res).unwrap();
+        assert_eq!(2, templates.len());
+        assert!(templates.iter().all(|template| template.remaining == 0));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoMetadataTemplateLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn templates_pick_is_deterministic() {
+        let mut first = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut second = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut first);
+        helper_instantiate(&mut second);
+
+        let info = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        let first_res = execute(
+            first.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        let second_res = execute(
+            second.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert_eq!(
+            minted_names(&first_res.unwrap()),
+            minted_names(&second_res.unwrap())
+        );
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::state::{State, SupplyKind};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, from_json, OwnedDeps};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 3,
+                market_supply: 2,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Airdrop {
+                receivers: vec![String::from("receiver_1"), String::from("receiver_2")],
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_state(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> State {
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        from_json(&res).unwrap()
+    }
+
+    #[test]
+    fn move_supply_non_admin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MoveSupply {
+                from: SupplyKind::Reserved,
+                to: SupplyKind::Market,
+                amount: 1,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn move_supply_invalid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MoveSupply {
+                from: SupplyKind::Market,
+                to: SupplyKind::Market,
+                amount: 1,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidSupplyMove {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn move_supply_minted() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // only 1 reserved token left unminted
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::MoveSupply {
+                from: SupplyKind::Reserved,
+                to: SupplyKind::Market,
+                amount: 2,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MoveSupply {
+                from: SupplyKind::Reserved,
+                to: SupplyKind::Market,
+                amount: 1,
+            },
+        );
+        assert!(res.is_ok());
+
+        let state = helper_state(&deps);
+        assert_eq!(2, state.total_reserved_supply);
+        assert_eq!(3, state.total_market_supply);
+    }
+
+    #[test]
+    fn auto_release_reserved() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateAutoReleaseReserved { enable: true },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SellMode { enable: false },
+        );
+        assert!(res.is_ok());
+
+        let state = helper_state(&deps);
+        assert_eq!(2, state.total_reserved_supply);
+        assert_eq!(3, state.total_market_supply);
+    }
+
+    #[test]
+    fn timelocked_auto_release_reserved() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        for msg in [
+            ExecuteMsg::UpdateAutoReleaseReserved { enable: true },
+            ExecuteMsg::UpdateTimelockDelay { delay: 60 },
+            ExecuteMsg::SellMode { enable: false },
+        ] {
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+            assert!(res.is_ok());
+        }
+
+        // the release waits for the timelock
+        let state = helper_state(&deps);
+        assert_eq!(3, state.total_reserved_supply);
+        assert_eq!(2, state.total_market_supply);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let state = helper_state(&deps);
+        assert_eq!(2, state.total_reserved_supply);
+        assert_eq!(3, state.total_market_supply);
+    }
+
+    #[test]
+    fn no_auto_release_reserved() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SellMode { enable: false },
+        );
+        assert!(res.is_ok());
+
+        let state = helper_state(&deps);
+        assert_eq!(3, state.total_reserved_supply);
+        assert_eq!(2, state.total_market_supply);
+    }
+}
@@ -0,0 +1,343 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{
+        ExecuteMsg, OracleConfig, OraclePriceResponse, OracleQueryMsg, PriceForResponse, QueryMsg,
+    };
+    use crate::state::Discount;
+    use crate::ContractError;
+    use cosmwasm_std::{
+        coin, coins, to_json_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env,
+        MessageInfo, Response, StdResult, Uint128,
+    };
+    use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+    use serde::{Deserialize, Serialize};
+    use sha2::{Digest, Sha256};
+
+    const PRICE: Item<OraclePriceResponse> = Item::new("price");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum MockOracleExecuteMsg {
+        SetPrice { rate: Decimal, updated_at: u64 },
+    }
+
+    /// Oracle returning the last price set, whatever the denoms
+    fn mock_oracle() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            |deps: DepsMut, _env: Env, _info: MessageInfo, msg: MockOracleExecuteMsg| {
+                let MockOracleExecuteMsg::SetPrice { rate, updated_at } = msg;
+                PRICE.save(deps.storage, &OraclePriceResponse { rate, updated_at })?;
+                StdResult::Ok(Response::new())
+            },
+            |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| {
+                StdResult::Ok(Response::new())
+            },
+            |deps: Deps, _env: Env, msg: OracleQueryMsg| -> StdResult<Binary> {
+                let OracleQueryMsg::Price { .. } = msg;
+                to_json_binary(&PRICE.load(deps.storage)?)
+            },
+        ))
+    }
+
+    /// NFT contract accepting any mint
+    fn mock_nft() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(
+            |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| {
+                StdResult::Ok(Response::new())
+            },
+            |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| {
+                StdResult::Ok(Response::new())
+            },
+            |_deps: Deps, _env: Env, _msg: Empty| -> StdResult<Binary> {
+                to_json_binary(&Empty {})
+            },
+        ))
+    }
+
+    fn sell_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+
+    fn owner() -> Addr {
+        Addr::unchecked("owner_addr")
+    }
+
+    /// Sell price of 10 uusd, paid in ujuno at 4 ujuno per uusd on both oracles
+    fn helper_instantiate() -> (App, Addr, Addr, Addr) {
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("alice"), coins(1000, "ujuno"))
+                .unwrap();
+        });
+
+        let oracle_id = app.store_code(mock_oracle());
+        let oracle = app
+            .instantiate_contract(oracle_id, owner(), &Empty {}, &[], "oracle", None)
+            .unwrap();
+        helper_set_rate(&mut app, &oracle, Decimal::percent(400), 0);
+        let check_oracle = app
+            .instantiate_contract(oracle_id, owner(), &Empty {}, &[], "check oracle", None)
+            .unwrap();
+        helper_set_rate(&mut app, &check_oracle, Decimal::percent(400), 0);
+
+        let nft_id = app.store_code(mock_nft());
+        let nft = app
+            .instantiate_contract(nft_id, owner(), &Empty {}, &[], "nft", None)
+            .unwrap();
+
+        let sell_id = app.store_code(sell_contract());
+        let sell = app
+            .instantiate_contract(
+                sell_id,
+                owner(),
+                &InitMsg {
+                    sell_mode: true,
+                    pre_sell_mode: false,
+                    max_buy_at_once: 5,
+                },
+                &[],
+                "sell",
+                None,
+            )
+            .unwrap();
+
+        let msgs = vec![
+            ExecuteMsg::UpdateNftContract {
+                address: nft.to_string(),
+            },
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, "uusd"),
+            },
+            ExecuteMsg::UpdateOracle {
+                oracle: Some(OracleConfig {
+                    contract: oracle.to_string(),
+                    check_contract: check_oracle.to_string(),
+                    reference_denom: String::from("uusd"),
+                    payment_denom: String::from("ujuno"),
+                    max_staleness: 3600,
+                    max_deviation: Decimal::percent(10),
+                }),
+            },
+        ];
+        for msg in msgs {
+            app.execute_contract(owner(), sell.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        (app, sell, oracle, check_oracle)
+    }
+
+    fn helper_set_rate(app: &mut App, oracle: &Addr, rate: Decimal, age: u64) {
+        let updated_at = app.block_info().time.seconds() - age;
+        app.execute_contract(
+            owner(),
+            oracle.clone(),
+            &MockOracleExecuteMsg::SetPrice { rate, updated_at },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn helper_buy(
+        app: &mut App,
+        sell: &Addr,
+        funds: &[Coin],
+        max_price: Option<Coin>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            Addr::unchecked("alice"),
+            sell.clone(),
+            &ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price,
+            },
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[test]
+    fn update_oracle_unauthorized() {
+        let (mut app, sell, _, _) = helper_instantiate();
+
+        let res = app.execute_contract(
+            Addr::unchecked("alice"),
+            sell,
+            &ExecuteMsg::UpdateOracle { oracle: None },
+            &[],
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap().downcast().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn buy_at_oracle_price() {
+        let (mut app, sell, _, _) = helper_instantiate();
+
+        let res: PriceForResponse = app
+            .wrap()
+            .query_wasm_smart(
+                sell.clone(),
+                &QueryMsg::PriceFor {
+                    address: String::from("alice"),
+                    quantity: 2,
+                },
+            )
+            .unwrap();
+        assert_eq!(coin(40, "ujuno"), res.unit_price);
+        assert_eq!(coin(80, "ujuno"), res.price);
+
+        let res = helper_buy(&mut app, &sell, &coins(39, "ujuno"), None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy(&mut app, &sell, &coins(40, "ujuno"), None);
+        assert!(res.is_ok());
+        assert_eq!(
+            coin(40, "ujuno"),
+            app.wrap().query_balance(sell, "ujuno").unwrap()
+        );
+    }
+
+    #[test]
+    fn oracle_price_bounds() {
+        let (mut app, sell, oracle, check_oracle) = helper_instantiate();
+
+        helper_set_rate(&mut app, &oracle, Decimal::percent(400), 3601);
+        let res = helper_buy(&mut app, &sell, &coins(40, "ujuno"), None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::OraclePriceStale {} => {}
+            _ => unreachable!(),
+        }
+
+        helper_set_rate(&mut app, &oracle, Decimal::percent(400), 3600);
+        helper_set_rate(&mut app, &check_oracle, Decimal::percent(400), 3601);
+        let res = helper_buy(&mut app, &sell, &coins(40, "ujuno"), None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::OraclePriceStale {} => {}
+            _ => unreachable!(),
+        }
+
+        helper_set_rate(&mut app, &check_oracle, Decimal::percent(400), 0);
+        let res = helper_buy(&mut app, &sell, &coins(40, "ujuno"), None);
+        assert!(res.is_ok());
+
+        // 25% above the check oracle
+        helper_set_rate(&mut app, &oracle, Decimal::percent(500), 0);
+        let res = helper_buy(&mut app, &sell, &coins(50, "ujuno"), None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::OraclePriceDeviation {} => {}
+            _ => unreachable!(),
+        }
+
+        // the market moved, both sources agree
+        helper_set_rate(&mut app, &check_oracle, Decimal::percent(480), 0);
+        let res = helper_buy(&mut app, &sell, &coins(50, "ujuno"), None);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn buy_max_price() {
+        let (mut app, sell, oracle, _) = helper_instantiate();
+
+        helper_set_rate(&mut app, &oracle, Decimal::percent(430), 0);
+        let res = helper_buy(
+            &mut app,
+            &sell,
+            &coins(43, "ujuno"),
+            Some(coin(42, "ujuno")),
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PriceAboveMax { price } => assert_eq!(coin(43, "ujuno"), price),
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy(&mut app, &sell, &coins(43, "ujuno"), Some(coin(43, "uusd")));
+        assert!(res.is_err());
+
+        let res = helper_buy(
+            &mut app,
+            &sell,
+            &coins(43, "ujuno"),
+            Some(coin(43, "ujuno")),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn buy_with_fixed_voucher() {
+        let (mut app, sell, _, _) = helper_instantiate();
+
+        app.execute_contract(
+            owner(),
+            sell.clone(),
+            &ExecuteMsg::CreateVoucher {
+                code_hash: hex::encode(Sha256::digest("PARTNER".as_bytes())),
+                discount: Discount::Fixed(Uint128::new(3)),
+                max_redemptions: 10,
+                expires: None,
+                allowed_address: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 3 ujuno off the converted price of 40 ujuno
+        let msg = ExecuteMsg::Buy {
+            permit: None,
+            referrer: None,
+            voucher: Some(String::from("PARTNER")),
+            recipient: None,
+            product_id: None,
+            collection_id: None,
+            max_price: None,
+        };
+        let res = app.execute_contract(
+            Addr::unchecked("alice"),
+            sell.clone(),
+            &msg,
+            &coins(36, "ujuno"),
+        );
+        assert!(res.is_err());
+        match res.err().unwrap().downcast().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = app.execute_contract(
+            Addr::unchecked("alice"),
+            sell.clone(),
+            &msg,
+            &coins(37, "ujuno"),
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            coin(37, "ujuno"),
+            app.wrap().query_balance(sell, "ujuno").unwrap()
+        );
+    }
+}
@@ -0,0 +1,444 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate};
+    use crate::msg::InitMsg;
+    use crate::msg::{CollectionConfig, ExecuteMsg, Permit, PermitPayload};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, to_json_vec, Binary, Coin, OwnedDeps};
+    use cw_carbonable_lib::Metadata;
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use k256::ecdsa::{Signature, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32].into()).unwrap()
+    }
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: true,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(4, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let pubkey = signing_key()
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetPermitSigner {
+                pubkey: Binary::from(pubkey),
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_permit(buyer: &str, max_quantity: u32, price: Coin, nonce: u64) -> Permit {
+        let payload = PermitPayload {
+            contract: mock_env().contract.address.to_string(),
+            buyer: String::from(buyer),
+            max_quantity,
+            price,
+            expires: mock_env().block.time.seconds() + 60,
+            nonce,
+            product_id: None,
+            collection_id: None,
+        };
+        sign_permit(payload)
+    }
+
+    fn sign_permit(payload: PermitPayload) -> Permit {
+        let hash = Sha256::digest(to_json_vec(&payload).unwrap());
+        let signature: Signature = signing_key().sign_prehash(&hash).unwrap();
+
+        Permit {
+            buyer: payload.buyer,
+            max_quantity: payload.max_quantity,
+            price: payload.price,
+            expires: payload.expires,
+            nonce: payload.nonce,
+            signature: Binary::from(signature.to_bytes().to_vec()),
+        }
+    }
+
+    #[test]
+    fn set_permit_signer_admin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddAdmin {
+                address: String::from("admin_addr"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetPermitSigner {
+                pubkey: Binary::from(vec![2u8; 33]),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_ok_not_whitelisted() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // permit price replaces the sell price
+        let permit = helper_permit("test", 2, coin(3, String::from("juno")), 1);
+        let info = mock_info("test", &[coin(6, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(2, res.messages.len());
+    }
+
+    #[test]
+    fn permit_replay() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let info = mock_info("test", &[coin(4, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Buy {
+                permit: Some(permit.clone()),
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PermitNonceUsed { nonce: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_tampered() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let mut permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        permit.price = coin(1, String::from("juno"));
+
+        let info = mock_info("test", &[coin(1, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidPermitSignature {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_other_buyer() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let info = mock_info("thief", &[coin(4, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidPermit {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_expired() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(61);
+
+        let info = mock_info("test", &[coin(4, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::Buy {
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PermitExpired {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_quantity_exceeded() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let info = mock_info("test", &[coin(8, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: Some(permit),
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PermitQuantityExceeded {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn permit_product() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetProduct {
+                product_id: 1,
+                name: String::from("10 m2"),
+                price: coin(10, String::from("juno")),
+                supply: 2,
+                metadata: Metadata::default(),
+            },
+        );
+        assert!(res.is_ok());
+
+        // a main sale permit doesn't buy the product at the permit price
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let msg = |permit| ExecuteMsg::Buy {
+            permit: Some(permit),
+            referrer: None,
+            voucher: None,
+            recipient: None,
+            product_id: Some(1),
+            collection_id: None,
+            max_price: None,
+        };
+        let info = mock_info("test", &[coin(4, String::from("juno"))]);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg(permit));
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidPermitSignature {} => {}
+            _ => unreachable!(),
+        }
+
+        let permit = sign_permit(PermitPayload {
+            contract: mock_env().contract.address.to_string(),
+            buyer: String::from("test"),
+            max_quantity: 1,
+            price: coin(4, String::from("juno")),
+            expires: mock_env().block.time.seconds() + 60,
+            nonce: 2,
+            product_id: Some(1),
+            collection_id: None,
+        });
+        let res = execute(deps.as_mut(), mock_env(), info, msg(permit));
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn permit_collection() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetCollection {
+                collection_id: 1,
+                config: CollectionConfig {
+                    name: String::from("mangroves"),
+                    nft_contract: String::from("mangroves_nft"),
+                    price: coin(10, String::from("juno")),
+                    supply: 3,
+                    metadata: Metadata::default(),
+                },
+            },
+        );
+        assert!(res.is_ok());
+
+        // a main sale permit doesn't buy from the collection at the permit price
+        let permit = helper_permit("test", 1, coin(4, String::from("juno")), 1);
+        let msg = |permit| ExecuteMsg::Buy {
+            permit: Some(permit),
+            referrer: None,
+            voucher: None,
+            recipient: None,
+            product_id: None,
+            collection_id: Some(1),
+            max_price: None,
+        };
+        let info = mock_info("test", &[coin(4, String::from("juno"))]);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg(permit));
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidPermitSignature {} => {}
+            _ => unreachable!(),
+        }
+
+        let permit = sign_permit(PermitPayload {
+            contract: mock_env().contract.address.to_string(),
+            buyer: String::from("test"),
+            max_quantity: 1,
+            price: coin(4, String::from("juno")),
+            expires: mock_env().block.time.seconds() + 60,
+            nonce: 2,
+            product_id: None,
+            collection_id: Some(1),
+        });
+        let res = execute(deps.as_mut(), mock_env(), info, msg(permit));
+        assert!(res.is_ok());
+    }
+}
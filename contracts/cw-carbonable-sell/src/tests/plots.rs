@@ -0,0 +1,296 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, PlotResponse, QueryMsg};
+    use crate::state::{PlotEntry, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, CosmosMsg, OwnedDeps, Response, WasmMsg};
+    use cw_carbonable_lib::{Metadata, Trait};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_set_plots(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        token_ids: &[u32],
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("owner_addr", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetPlots {
+                plots: token_ids
+                    .iter()
+                    .map(|token_id| PlotEntry {
+                        token_id: *token_id,
+                        price: coin(10 * *token_id as u128, String::from("juno")),
+                        metadata: Metadata {
+                            attributes: Some(vec![Trait {
+                                display_type: None,
+                                trait_type: String::from("coordinates"),
+                                value: format!("{}N 88W", token_id),
+                            }]),
+                            ..Default::default()
+                        },
+                    })
+                    .collect(),
+            },
+        )
+    }
+
+    fn helper_buy_specific(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        token_ids: Vec<u32>,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("test", &[coin(amount, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::BuySpecific {
+                token_ids,
+                max_price: None,
+            },
+        )
+    }
+
+    fn minted_id(msg: &CosmosMsg) -> String {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                    cw_carbonable_lib::ExecuteMsg::Mint(mint) => mint.token_id,
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn set_plots_taken_id() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = helper_set_plots(&mut deps, &[1]);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PlotIdTaken { token_id } => assert_eq!(1, token_id),
+            _ => unreachable!(),
+        }
+
+        // plots are taken from the market supply
+        let res = helper_set_plots(&mut deps, &[5, 6]);
+        assert!(res.is_ok());
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(3, state.total_market_minted);
+    }
+
+    #[test]
+    fn buy_specific_plots() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        assert!(helper_set_plots(&mut deps, &[5, 6, 7]).is_ok());
+
+        let res = helper_buy_specific(&mut deps, vec![7], 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy_specific(&mut deps, vec![5, 6], 110).unwrap();
+        assert_eq!("5", minted_id(&res.messages[0].msg));
+        assert_eq!("6", minted_id(&res.messages[1].msg));
+
+        let res = helper_buy_specific(&mut deps, vec![6], 60);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PlotSold { token_id } => assert_eq!(6, token_id),
+            _ => unreachable!(),
+        }
+
+        let res = helper_buy_specific(&mut deps, vec![8], 80);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PlotNotFound { token_id } => assert_eq!(8, token_id),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn sequential_ids_skip_plots() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        assert!(helper_set_plots(&mut deps, &[2]).is_ok());
+
+        let info = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+        .unwrap();
+        assert_eq!("1", minted_id(&res.messages[0].msg));
+        assert_eq!("3", minted_id(&res.messages[1].msg));
+    }
+
+    #[test]
+    fn query_plots_paginated() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        assert!(helper_set_plots(&mut deps, &[5, 6, 7]).is_ok());
+        assert!(helper_buy_specific(&mut deps, vec![6], 60).is_ok());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Plots {
+                start_after: Some(5),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+        let plots: Vec<PlotResponse> = from_json(&res).unwrap();
+        assert_eq!(1, plots.len());
+        assert_eq!(6, plots[0].token_id);
+        assert!(plots[0].plot.sold);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Plots {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let plots: Vec<PlotResponse> = from_json(&res).unwrap();
+        assert_eq!(3, plots.len());
+        assert!(!plots[2].plot.sold);
+    }
+
+    #[test]
+    fn buy_specific_max_price() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        assert!(helper_set_plots(&mut deps, &[5, 6]).is_ok());
+
+        let info = mock_info("test", &[coin(110, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::BuySpecific {
+                token_ids: vec![5, 6],
+                max_price: Some(coin(100, String::from("juno"))),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PriceAboveMax { price } => {
+                assert_eq!(coin(110, String::from("juno")), price)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn timelocked_plots() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateTimelockDelay { delay: 60 },
+        );
+        assert!(res.is_ok());
+
+        assert!(helper_set_plots(&mut deps, &[5]).is_ok());
+        let msg = QueryMsg::Plots {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg.clone()).unwrap();
+        let plots: Vec<PlotResponse> = from_json(&res).unwrap();
+        assert!(plots.is_empty());
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let plots: Vec<PlotResponse> = from_json(&res).unwrap();
+        assert_eq!(1, plots.len());
+        assert_eq!(coin(50, String::from("juno")), plots[0].plot.price);
+    }
+}
@@ -29,7 +29,20 @@ mod cw_carbonable {
         helper_instantiate(&mut deps);
 
         let info = mock_info("test", &[]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_err());
         match res.err().unwrap() {
@@ -54,7 +67,20 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let info = mock_info("test", &[]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_err());
         match res.err().unwrap() {
@@ -118,7 +144,21 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let info = mock_info("test", &[coin(8, String::from("juno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::MultiBuy { quantity: 2});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         println!("{:#?}", res);
         assert!(res.is_ok());
@@ -179,7 +219,21 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let info = mock_info("test", &[coin(8, String::from("juno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::MultiBuy { quantity: 2});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         println!("{:#?}", res);
         assert!(res.is_err());
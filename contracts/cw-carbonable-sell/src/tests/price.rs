@@ -8,7 +8,7 @@ mod cw_carbonable {
     use cosmwasm_std::testing::{
         mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
     };
-    use cosmwasm_std::{coin, coins, from_binary, OwnedDeps};
+    use cosmwasm_std::{coin, coins, from_json, OwnedDeps};
 
     fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
         let msg = InitMsg {
@@ -98,7 +98,20 @@ mod cw_carbonable {
             "cosmos1hdxjsex4frhtyzx68837fc7ssutp76kk4cyy4e",
             &[coin(18u128, String::from("ujuno"))],
         );
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_err());
         match res.err().unwrap() {
@@ -117,7 +130,20 @@ mod cw_carbonable {
             "cosmos1hdxjsex4frhtyzx68837fc7ssutp76kk4cyy4e",
             &[coin(18u128, String::from("uatom"))],
         );
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_err());
         match res.err().unwrap() {
@@ -136,12 +162,25 @@ mod cw_carbonable {
             "cosmos1hdxjsex4frhtyzx68837fc7ssutp76kk4cyy4e",
             &[coin(20u128, String::from("ujuno"))],
         );
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(1, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -173,7 +212,16 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 6 },
+            ExecuteMsg::MultiBuy {
+                quantity: 6,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_err());
@@ -187,7 +235,16 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 5 },
+            ExecuteMsg::MultiBuy {
+                quantity: 5,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_err());
@@ -204,7 +261,16 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 5 },
+            ExecuteMsg::MultiBuy {
+                quantity: 5,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_err());
@@ -214,7 +280,7 @@ mod cw_carbonable {
         }
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(9, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -228,13 +294,22 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 5 },
+            ExecuteMsg::MultiBuy {
+                quantity: 5,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(9, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -248,7 +323,16 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 5 },
+            ExecuteMsg::MultiBuy {
+                quantity: 5,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_err());
@@ -258,7 +342,7 @@ mod cw_carbonable {
         }
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(9, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -272,13 +356,22 @@ mod cw_carbonable {
             deps.as_mut(),
             mock_env(),
             info,
-            ExecuteMsg::MultiBuy { quantity: 4 },
+            ExecuteMsg::MultiBuy {
+                quantity: 4,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
         );
 
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(9, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, ProductResponse, QueryMsg};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, CosmosMsg, OwnedDeps, Response, WasmMsg};
+    use cw_carbonable_lib::Metadata;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        for (product_id, size, price) in [(1, 1, 3), (2, 10, 25)] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::SetProduct {
+                    product_id,
+                    name: format!("{} m2", size),
+                    price: coin(price, String::from("juno")),
+                    supply: 2,
+                    metadata: Metadata {
+                        name: Some(format!("Plot {} m2", size)),
+                        external_url: Some(format!("https://carbonable.io/plot-{}/", size)),
+                        ..Default::default()
+                    },
+                },
+            );
+            assert!(res.is_ok());
+        }
+    }
+
+    fn helper_buy_product(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        product_id: u32,
+        quantity: u32,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("test", &[coin(amount, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: Some(product_id),
+                collection_id: None,
+                max_price: None,
+            },
+        )
+    }
+
+    #[test]
+    fn set_product_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetProduct {
+                product_id: 3,
+                name: String::from("100 m2"),
+                price: coin(200, String::from("juno")),
+                supply: 1,
+                metadata: Metadata::default(),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn buy_unknown_product() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy_product(&mut deps, 3, 1, 200);
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::ProductNotFound { product_id: 3 } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn buy_product() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // product price, not the sell price
+        let res = helper_buy_product(&mut deps, 1, 2, 6).unwrap();
+
+        assert_eq!(2, res.messages.len());
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match from_json::<cw_carbonable_lib::ExecuteMsg>(msg).unwrap() {
+                    cw_carbonable_lib::ExecuteMsg::Mint(mint) => {
+                        assert_eq!("2", mint.token_id);
+                        assert_eq!(
+                            Some(String::from("https://carbonable.io/plot-1/2")),
+                            mint.token_uri
+                        );
+                        assert_eq!(
+                            Some(String::from("Plot 1 m2")),
+                            mint.extension.unwrap().name
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Products {}).unwrap();
+        let products: Vec<ProductResponse> = from_json(&res).unwrap();
+        assert_eq!(2, products.len());
+        assert_eq!(2, products[0].product.minted);
+        assert_eq!(0, products[1].product.minted);
+    }
+
+    #[test]
+    fn product_sold_out() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_buy_product(&mut deps, 2, 2, 50);
+        assert!(res.is_ok());
+
+        let res = helper_buy_product(&mut deps, 2, 1, 25);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        // supply below the minted tokens
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SetProduct {
+                product_id: 2,
+                name: String::from("10 m2"),
+                price: coin(25, String::from("juno")),
+                supply: 1,
+                metadata: Metadata::default(),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::state::ReferralStats;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, BankMsg, CosmosMsg, Decimal, OwnedDeps, SubMsg};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateReferralShare {
+                share: Decimal::percent(10),
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn update_referral_share_invalid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateReferralShare {
+                share: Decimal::percent(101),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidReferralShare {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn self_referral() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(10, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: Some(String::from("test")),
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SelfReferral {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn referral_rewards_claim() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(20, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: Some(String::from("partner")),
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ReferralStats {
+                address: String::from("partner"),
+            },
+        )
+        .unwrap();
+        let stats: ReferralStats = from_json(&res).unwrap();
+        assert_eq!(1, stats.nb_purchases);
+        assert_eq!(2, stats.nb_tokens);
+        assert_eq!(vec![coin(2, String::from("juno"))], stats.total_earned);
+        assert_eq!(vec![coin(2, String::from("juno"))], stats.claimable);
+
+        let info = mock_info("partner", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::ClaimReferralRewards {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("partner"),
+                amount: vec![coin(2, String::from("juno"))],
+            }))]
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ClaimReferralRewards {},
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NoReferralRewards {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ReferralStats {
+                address: String::from("partner"),
+            },
+        )
+        .unwrap();
+        let stats: ReferralStats = from_json(&res).unwrap();
+        assert_eq!(vec![coin(2, String::from("juno"))], stats.total_earned);
+        assert!(stats.claimable.is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, RevealEntry};
+    use crate::state::Provenance;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, to_json_vec, CosmosMsg, OwnedDeps, WasmMsg};
+    use cw_carbonable_lib::{Metadata, NftExecuteMsg};
+    use sha2::{Digest, Sha256};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 3,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn final_metadata() -> Vec<Metadata> {
+        ["Oak", "Pine", "Cedar"]
+            .iter()
+            .map(|name| Metadata {
+                name: Some(String::from(*name)),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn item_hashes() -> Vec<String> {
+        final_metadata()
+            .iter()
+            .map(|metadata| hex::encode(Sha256::digest(to_json_vec(metadata).unwrap())))
+            .collect()
+    }
+
+    fn provenance_hash() -> String {
+        hex::encode(Sha256::digest(item_hashes().concat().as_bytes()))
+    }
+
+    fn seed_hash() -> String {
+        hex::encode(Sha256::digest(b"secret"))
+    }
+
+    fn helper_sell_out(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::CommitProvenance {
+                provenance_hash: provenance_hash(),
+                seed_hash: seed_hash(),
+            },
+        );
+        assert!(res.is_ok());
+
+        let buyer = mock_info("test", &[coin(0, String::from("ujuno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            buyer,
+            ExecuteMsg::MultiBuy {
+                quantity: 3,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::SellMode { enable: false },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn commit_provenance_admin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddAdmin {
+                address: String::from("admin_addr"),
+            },
+        );
+        assert!(res.is_ok());
+
+        // only the owner commits the provenance
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CommitProvenance {
+                provenance_hash: provenance_hash(),
+                seed_hash: seed_hash(),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn commit_provenance_after_mint() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_sell_out(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CommitProvenance {
+                provenance_hash: provenance_hash(),
+                seed_hash: seed_hash(),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::ProvenanceLocked {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_hash_mismatch() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_sell_out(&mut deps);
+
+        let mut hashes = item_hashes();
+        hashes.swap(0, 1);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Reveal {
+                item_hashes: hashes,
+                seed: String::from("secret"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::ProvenanceHashMismatch {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_incomplete() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_sell_out(&mut deps);
+
+        // the supply grew after the commitment
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 4,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Reveal {
+                item_hashes: item_hashes(),
+                seed: String::from("secret"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::ProvenanceIncomplete {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_seed_mismatch() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_sell_out(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Reveal {
+                item_hashes: item_hashes(),
+                seed: String::from("guess"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::ProvenanceSeedMismatch {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_ok() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+        helper_sell_out(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Reveal {
+                item_hashes: item_hashes(),
+                seed: String::from("secret"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Provenance {}).unwrap();
+        let provenance: Option<Provenance> = from_json(&res).unwrap();
+        let offset = provenance.unwrap().offset.unwrap() as usize;
+
+        // token 1 gets the metadata at the offset
+        let metadata = final_metadata();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::RevealBatch {
+                entries: vec![RevealEntry {
+                    token_id: 1,
+                    metadata: metadata[(offset + 1) % 3].clone(),
+                }],
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidRevealMetadata { token_id: 1 } => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RevealBatch {
+                entries: (1..=3)
+                    .map(|token_id| RevealEntry {
+                        token_id,
+                        metadata: metadata[(token_id as usize - 1 + offset) % 3].clone(),
+                    })
+                    .collect(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(3, res.messages.len());
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                assert_eq!(
+                    NftExecuteMsg::UpdateMetadata {
+                        token_id: String::from("1"),
+                        extension: Some(metadata[offset].clone()),
+                    },
+                    from_json(msg).unwrap()
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, RoundResponse};
+    use crate::state::RoundConfig;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, Env, OwnedDeps, Response};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetRound {
+                round_id: 1,
+                config: RoundConfig {
+                    name: String::from("early supporters"),
+                    price: coin(5, String::from("juno")),
+                    supply: 3,
+                    start: mock_env().block.time.seconds(),
+                    end: mock_env().block.time.seconds() + 100,
+                    whitelist_only: true,
+                    max_per_wallet: Some(2),
+                    carry_forward: true,
+                },
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetRound {
+                round_id: 2,
+                config: RoundConfig {
+                    name: String::from("public"),
+                    price: coin(10, String::from("juno")),
+                    supply: 2,
+                    start: mock_env().block.time.seconds() + 100,
+                    end: mock_env().block.time.seconds() + 200,
+                    whitelist_only: false,
+                    max_per_wallet: None,
+                    carry_forward: false,
+                },
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::AddToRoundWhitelist {
+                round_id: 1,
+                addresses: vec![String::from("supporter")],
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ActivateRound { round_id: 1 },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn later(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn helper_multi_buy(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        buyer: &str,
+        quantity: u32,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(buyer, &[coin(amount, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        )
+    }
+
+    #[test]
+    fn set_round_invalid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetRound {
+                round_id: 3,
+                config: RoundConfig {
+                    name: String::from("partners"),
+                    price: coin(5, String::from("juno")),
+                    supply: 3,
+                    start: 100,
+                    end: 100,
+                    whitelist_only: true,
+                    max_per_wallet: None,
+                    carry_forward: false,
+                },
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidRound {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RemoveRound { round_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RoundOpen { round_id: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn round_whitelist_and_wallet_limit() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_multi_buy(&mut deps, mock_env(), "test", 1, 5);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AddressNotWhitelisted {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_multi_buy(&mut deps, mock_env(), "supporter", 2, 10);
+        assert!(res.is_ok());
+
+        let res = helper_multi_buy(&mut deps, mock_env(), "supporter", 1, 5);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RoundWalletLimitReached {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn round_window() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_multi_buy(&mut deps, later(100), "supporter", 1, 5);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RoundEnded {} => {}
+            _ => unreachable!(),
+        }
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ActivateRound { round_id: 2 },
+        );
+        assert!(res.is_ok());
+
+        let res = helper_multi_buy(&mut deps, later(99), "test", 1, 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RoundNotStarted {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn round_carry_forward_and_stats() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_multi_buy(&mut deps, mock_env(), "supporter", 1, 5);
+        assert!(res.is_ok());
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ActivateRound { round_id: 2 },
+        );
+        assert!(res.is_ok());
+
+        // 2 tokens carried from the first round
+        let res = helper_multi_buy(&mut deps, later(150), "test", 4, 40);
+        assert!(res.is_ok());
+        let res = helper_multi_buy(&mut deps, later(150), "test", 1, 10);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Rounds {}).unwrap();
+        let rounds: Vec<RoundResponse> = from_json(&res).unwrap();
+        assert_eq!(2, rounds.len());
+
+        assert!(!rounds[0].open);
+        assert_eq!(1, rounds[0].stats.nb_tokens);
+        assert_eq!(1, rounds[0].stats.nb_buyers);
+        assert_eq!(vec![coin(5, String::from("juno"))], rounds[0].stats.revenue);
+        assert_eq!(2, rounds[0].stats.carried_out);
+        assert_eq!(0, rounds[0].remaining);
+
+        assert!(rounds[1].open);
+        assert_eq!(4, rounds[1].stats.nb_tokens);
+        assert_eq!(
+            vec![coin(40, String::from("juno"))],
+            rounds[1].stats.revenue
+        );
+        assert_eq!(2, rounds[1].stats.carried_in);
+        assert_eq!(0, rounds[1].remaining);
+    }
+
+    #[test]
+    fn close_round() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::CloseRound {});
+        assert!(res.is_ok());
+
+        // back to the sell modes
+        let res = helper_multi_buy(&mut deps, mock_env(), "supporter", 1, 5);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SellClose {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Round { round_id: 1 }).unwrap();
+        let round: RoundResponse = from_json(&res).unwrap();
+        assert!(!round.open);
+        assert_eq!(3, round.remaining);
+    }
+}
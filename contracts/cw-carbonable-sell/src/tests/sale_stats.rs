@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, SaleStatsResponse, WhiteListEntry};
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, Decimal, OwnedDeps};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: false,
+            pre_sell_mode: true,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 2,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddToWhitelist {
+                entries: vec![WhiteListEntry {
+                    address: String::from("early"),
+                    nb_slots: 2,
+                }],
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_multi_buy(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        buyer: &str,
+        quantity: u32,
+        seconds: u64,
+    ) {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+
+        let info = mock_info(buyer, &[coin(10 * quantity as u128, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn no_sale_stats() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SaleStats {}).unwrap();
+        let stats: SaleStatsResponse = from_json(&res).unwrap();
+        assert!(stats.revenue.is_empty());
+        assert_eq!(0, stats.nb_buyers);
+        assert_eq!(Decimal::zero(), stats.average_tokens_per_buyer);
+        assert_eq!(None, stats.first_sale);
+        assert_eq!(None, stats.last_sale);
+    }
+
+    #[test]
+    fn sale_stats() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        helper_multi_buy(&mut deps, "early", 2, 10);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SellMode { enable: true },
+        );
+        assert!(res.is_ok());
+
+        helper_multi_buy(&mut deps, "early", 1, 20);
+        helper_multi_buy(&mut deps, "late", 3, 30);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Airdrop {
+                receivers: vec![String::from("receiver")],
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SaleStats {}).unwrap();
+        let stats: SaleStatsResponse = from_json(&res).unwrap();
+        assert_eq!(vec![coin(60, String::from("juno"))], stats.revenue);
+        assert_eq!(2, stats.nb_buyers);
+        assert_eq!(2, stats.pre_sale_tokens);
+        assert_eq!(4, stats.public_sale_tokens);
+        assert_eq!(1, stats.airdrop_tokens);
+        assert_eq!(
+            Decimal::from_ratio(3u32, 1u32),
+            stats.average_tokens_per_buyer
+        );
+        assert_eq!(Some(mock_env().block.time.seconds() + 10), stats.first_sale);
+        assert_eq!(Some(mock_env().block.time.seconds() + 30), stats.last_sale);
+    }
+}
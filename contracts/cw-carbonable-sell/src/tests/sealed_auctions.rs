@@ -0,0 +1,353 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, SealedAuctionResponse, SealedBidPayload};
+    use crate::state::{SealedAuctionConfig, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_json, to_json_vec, BankMsg, CosmosMsg, Env, OwnedDeps, Response,
+        Uint128,
+    };
+    use cw_carbonable_lib::Metadata;
+    use sha2::{Digest, Sha256};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, quantity: u32) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let now = mock_env().block.time.seconds();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateSealedAuction {
+                config: SealedAuctionConfig {
+                    metadata: Metadata {
+                        name: Some(String::from("New project")),
+                        ..Default::default()
+                    },
+                    denom: String::from("juno"),
+                    reserve_price: Uint128::new(100),
+                    quantity,
+                    commit_start: now,
+                    commit_end: now + 3600,
+                    reveal_end: now + 7200,
+                },
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn commitment(bidder: &str, price: u128, salt: &str) -> String {
+        let payload = SealedBidPayload {
+            contract: mock_env().contract.address.to_string(),
+            auction_id: 1,
+            bidder: String::from(bidder),
+            price: Uint128::new(price),
+            salt: String::from(salt),
+        };
+        hex::encode(Sha256::digest(to_json_vec(&payload).unwrap()))
+    }
+
+    fn helper_commit(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        bidder: &str,
+        price: u128,
+        deposit: u128,
+    ) {
+        let info = mock_info(bidder, &[coin(deposit, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CommitSealedBid {
+                auction_id: 1,
+                commitment: commitment(bidder, price, "salt"),
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_reveal(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        bidder: &str,
+        price: u128,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(bidder, &[]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::RevealSealedBid {
+                auction_id: 1,
+                price: Uint128::new(price),
+                salt: String::from("salt"),
+            },
+        )
+    }
+
+    fn helper_settle(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info("anyone", &[]);
+        execute(
+            deps.as_mut(),
+            env_at(7200),
+            info,
+            ExecuteMsg::SettleSealedAuction {
+                auction_id: 1,
+                limit,
+            },
+        )
+    }
+
+    fn helper_claim_refund(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        bidder: &str,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(bidder, &[]);
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ClaimSealedRefund { auction_id: 1 },
+        )
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    #[test]
+    fn commit_below_reserve() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 2);
+
+        let info = mock_info("alice", &[coin(99, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CommitSealedBid {
+                auction_id: 1,
+                commitment: commitment("alice", 99, "salt"),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InsufficientDeposit {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_sealed_bid() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 2);
+        helper_commit(&mut deps, "alice", 150, 200);
+
+        let res = helper_reveal(&mut deps, mock_env(), "alice", 150);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::RevealPhaseNotStarted {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_reveal(&mut deps, env_at(3600), "alice", 140);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SealedBidMismatch {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_reveal(&mut deps, env_at(3600), "alice", 150);
+        assert!(res.is_ok());
+
+        let res = helper_reveal(&mut deps, env_at(3600), "alice", 150);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SealedBidNotFound {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn reveal_above_deposit() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 2);
+        helper_commit(&mut deps, "alice", 250, 200);
+
+        let res = helper_reveal(&mut deps, env_at(3600), "alice", 250);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InsufficientDeposit {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn settle_uniform_clearing_price() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 2);
+        helper_commit(&mut deps, "alice", 150, 200);
+        helper_commit(&mut deps, "bob", 120, 120);
+        helper_commit(&mut deps, "carol", 100, 150);
+        helper_commit(&mut deps, "dave", 300, 300);
+
+        for (bidder, price) in [("alice", 150), ("bob", 120), ("carol", 100)] {
+            let res = helper_reveal(&mut deps, env_at(3600), bidder, price);
+            assert!(res.is_ok());
+        }
+
+        let res = helper_settle(&mut deps, None).unwrap();
+        assert!(res.attributes.contains(&attr("winner", "alice")));
+        assert!(res.attributes.contains(&attr("winner", "bob")));
+        assert!(res.attributes.contains(&attr("clearing_price", "120juno")));
+
+        // 2 mints, bob deposit is fully spent
+        assert_eq!(3, res.messages.len());
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: vec![coin(80, String::from("juno"))],
+            }),
+            res.messages[1].msg
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SealedAuctions {}).unwrap();
+        let auctions: Vec<SealedAuctionResponse> = from_json(&res).unwrap();
+        assert!(auctions.is_empty());
+    }
+
+    #[test]
+    fn settle_releases_unsold_tokens() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 3);
+        helper_commit(&mut deps, "alice", 150, 200);
+
+        let res = helper_settle(&mut deps, None);
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(0, state.total_market_minted);
+
+        let res = helper_settle(&mut deps, None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AuctionEnded {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn settle_in_batches() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 3);
+        for (bidder, price) in [("alice", 150), ("bob", 120), ("carol", 100)] {
+            helper_commit(&mut deps, bidder, price, price);
+            let res = helper_reveal(&mut deps, env_at(3600), bidder, price);
+            assert!(res.is_ok());
+        }
+
+        let res = helper_settle(&mut deps, Some(2)).unwrap();
+        assert!(res.attributes.contains(&attr("clearing_price", "100juno")));
+        assert!(res.attributes.contains(&attr("winner", "alice")));
+        assert!(res.attributes.contains(&attr("winner", "bob")));
+        assert!(!res.attributes.contains(&attr("winner", "carol")));
+
+        let res = helper_settle(&mut deps, Some(2)).unwrap();
+        assert!(res.attributes.contains(&attr("winner", "carol")));
+        assert_eq!(1, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(3, state.total_market_minted);
+
+        let res = helper_settle(&mut deps, None);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AuctionEnded {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn claim_sealed_refund() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps, 1);
+        helper_commit(&mut deps, "alice", 150, 200);
+        helper_commit(&mut deps, "bob", 120, 150);
+        helper_commit(&mut deps, "carol", 300, 300);
+
+        for (bidder, price) in [("alice", 150), ("bob", 120)] {
+            let res = helper_reveal(&mut deps, env_at(3600), bidder, price);
+            assert!(res.is_ok());
+        }
+
+        let res = helper_claim_refund(&mut deps, env_at(3600), "bob");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::AuctionNotEnded {} => {}
+            _ => unreachable!(),
+        }
+
+        // the winner is paid out by the settlement
+        let res = helper_claim_refund(&mut deps, env_at(7200), "alice");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SealedBidNotFound {} => {}
+            _ => unreachable!(),
+        }
+
+        // losing and unrevealed bids get their whole deposit back
+        for (bidder, deposit) in [("bob", 150), ("carol", 300)] {
+            let res = helper_claim_refund(&mut deps, env_at(7200), bidder).unwrap();
+            assert_eq!(
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: String::from(bidder),
+                    amount: vec![coin(deposit, String::from("juno"))],
+                }),
+                res.messages[0].msg
+            );
+
+            let res = helper_claim_refund(&mut deps, env_at(7200), bidder);
+            assert!(res.is_err());
+        }
+    }
+}
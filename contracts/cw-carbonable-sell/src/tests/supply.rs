@@ -8,7 +8,7 @@ mod cw_carbonable {
     use cosmwasm_std::testing::{
         mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
     };
-    use cosmwasm_std::{coin, coins, from_binary, OwnedDeps};
+    use cosmwasm_std::{coin, coins, from_json, OwnedDeps};
 
     fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
         let msg = InitMsg {
@@ -68,7 +68,7 @@ mod cw_carbonable {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -95,7 +95,7 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -115,7 +115,7 @@ mod cw_carbonable {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(0, state.total_reserved_supply);
         assert_eq!(0, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -142,7 +142,7 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -163,7 +163,7 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(1, state.total_reserved_minted);
@@ -206,19 +206,32 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
         assert_eq!(0, state.total_market_minted);
 
         let info = mock_info("owner_addr", &[coin(2_u128, String::from("ujuno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
@@ -261,31 +274,70 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
         assert_eq!(0, state.total_market_minted);
 
         let info = mock_info("owner_addr", &[coin(2_u128, String::from("ujuno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_ok());
 
         let info = mock_info("owner_addr", &[coin(2_u128, String::from("ujuno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(0, state.total_reserved_minted);
         assert_eq!(2, state.total_market_minted);
 
         let info = mock_info("owner_addr", &[coin(2_u128, String::from("ujuno"))]);
-        let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Buy {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
 
         assert!(res.is_err());
 
@@ -311,7 +363,7 @@ mod cw_carbonable {
         assert!(res.is_ok());
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(2, state.total_reserved_supply);
         assert_eq!(2, state.total_market_supply);
         assert_eq!(2, state.total_reserved_minted);
@@ -0,0 +1,410 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{
+        ExecuteMsg, OracleConfig, PendingChangesResponse, ProductResponse, QueryMsg, RoundResponse,
+    };
+    use crate::state::{ConfigChange, Oracle, RoundConfig, State};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, Addr, Binary, Decimal, Env, OwnedDeps};
+    use cw_carbonable_lib::Metadata;
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::AddAdmin {
+                address: String::from("admin_addr"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateTimelockDelay { delay: 60 },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn later(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    #[test]
+    fn update_timelock_delay_admin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateTimelockDelay { delay: 0 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn timelocked_price() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(coin(0, String::from("ujuno")), state.sell_price);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingChanges {}).unwrap();
+        let pending: PendingChangesResponse = from_json(&res).unwrap();
+        assert_eq!(60, pending.delay);
+        assert_eq!(1, pending.changes.len());
+        assert_eq!(1, pending.changes[0].change_id);
+        assert_eq!(
+            ConfigChange::Price(coin(10, String::from("juno"))),
+            pending.changes[0].change
+        );
+        assert_eq!(
+            mock_env().block.time.seconds() + 60,
+            pending.changes[0].activation
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            later(59),
+            info.clone(),
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PendingChangeNotReady { change_id: 1, .. } => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(coin(10, String::from("juno")), state.sell_price);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingChanges {}).unwrap();
+        let pending: PendingChangesResponse = from_json(&res).unwrap();
+        assert!(pending.changes.is_empty());
+    }
+
+    #[test]
+    fn timelocked_change_cancel() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateNftContract {
+                address: String::from("nft_addr"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::CancelPendingChange { change_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        let owner = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner,
+            ExecuteMsg::CancelPendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PendingChangeNotFound { change_id: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn timelocked_delay() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // removing the timelock waits for the current delay
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateTimelockDelay { delay: 0 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingChanges {}).unwrap();
+        let pending: PendingChangesResponse = from_json(&res).unwrap();
+        assert_eq!(60, pending.delay);
+        assert_eq!(ConfigChange::TimelockDelay(0), pending.changes[0].change);
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info.clone(),
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 1,
+                market_supply: 2,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
+        let state: State = from_json(&res).unwrap();
+        assert_eq!(2, state.total_market_supply);
+    }
+
+    #[test]
+    fn timelocked_round() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let config = RoundConfig {
+            name: String::from("public"),
+            price: coin(1, String::from("juno")),
+            supply: 5,
+            start: mock_env().block.time.seconds(),
+            end: mock_env().block.time.seconds() + 100,
+            whitelist_only: false,
+            max_per_wallet: None,
+            carry_forward: false,
+        };
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetRound {
+                round_id: 1,
+                config: config.clone(),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Round { round_id: 1 });
+        assert!(res.is_err());
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Round { round_id: 1 }).unwrap();
+        let round: RoundResponse = from_json(&res).unwrap();
+        assert_eq!(config, round.config);
+    }
+
+    #[test]
+    fn timelocked_product() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetProduct {
+                product_id: 1,
+                name: String::from("10 m2"),
+                price: coin(1, String::from("juno")),
+                supply: 2,
+                metadata: Metadata::default(),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Products {}).unwrap();
+        let products: Vec<ProductResponse> = from_json(&res).unwrap();
+        assert!(products.is_empty());
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Products {}).unwrap();
+        let products: Vec<ProductResponse> = from_json(&res).unwrap();
+        assert_eq!(1, products.len());
+        assert_eq!(coin(1, String::from("juno")), products[0].product.price);
+    }
+
+    #[test]
+    fn timelocked_permit_signer() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::SetPermitSigner {
+                pubkey: Binary::from(vec![2u8; 33]),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingChanges {}).unwrap();
+        let pending: PendingChangesResponse = from_json(&res).unwrap();
+        assert_eq!(1, pending.changes.len());
+        assert_eq!(
+            ConfigChange::PermitSigner(Binary::from(vec![2u8; 33])),
+            pending.changes[0].change
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn timelocked_oracle() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateOracle {
+                oracle: Some(OracleConfig {
+                    contract: String::from("oracle_addr"),
+                    check_contract: String::from("check_oracle_addr"),
+                    reference_denom: String::from("uusd"),
+                    payment_denom: String::from("ujuno"),
+                    max_staleness: 3600,
+                    max_deviation: Decimal::percent(10),
+                }),
+            },
+        );
+        assert!(res.is_ok());
+
+        // the sale is not repriced before the delay
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Oracle {}).unwrap();
+        let oracle: Option<Oracle> = from_json(&res).unwrap();
+        assert_eq!(None, oracle);
+
+        let res = execute(
+            deps.as_mut(),
+            later(59),
+            info.clone(),
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PendingChangeNotReady { change_id: 1, .. } => {}
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            later(60),
+            info,
+            ExecuteMsg::ExecutePendingChange { change_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Oracle {}).unwrap();
+        let oracle: Option<Oracle> = from_json(&res).unwrap();
+        assert_eq!(
+            Some(Addr::unchecked("oracle_addr")),
+            oracle.map(|oracle| oracle.contract)
+        );
+    }
+}
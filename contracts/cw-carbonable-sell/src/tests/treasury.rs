@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, TreasuryResponse};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coin, coins, from_json, Addr, BankMsg, CosmosMsg, Decimal, OwnedDeps, SubMsg,
+    };
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateReferralShare {
+                share: Decimal::percent(10),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddAdmin {
+                address: String::from("admin_addr"),
+            },
+        );
+        assert!(res.is_ok());
+
+        // 2 juno owed to the referrer
+        let info = mock_info("test", &[coin(20, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: Some(String::from("partner")),
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn add_treasury_wallet_admin() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddTreasuryWallet {
+                address: String::from("admin_addr"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_not_treasury_wallet() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Withdraw {
+                wallet: String::from("admin_addr"),
+                coin: coins(10, "juno"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotTreasuryWallet { address } => assert_eq!("admin_addr", address),
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::WithdrawAll {
+                wallet: String::from("admin_addr"),
+                denom: String::from("juno"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotTreasuryWallet { .. } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_keeps_referral_rewards() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Withdraw {
+                wallet: String::from("owner_addr"),
+                coin: coins(99, "juno"),
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InsufficientFunds { available } => {
+                assert_eq!(coin(98, "juno"), available)
+            }
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::WithdrawAll {
+                wallet: String::from("owner_addr"),
+                denom: String::from("juno"),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("owner_addr"),
+                amount: coins(98, "juno"),
+            }))]
+        );
+    }
+
+    #[test]
+    fn treasury_query() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::AddTreasuryWallet {
+                address: String::from("cold_wallet"),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Treasury {}).unwrap();
+        let treasury: TreasuryResponse = from_json(&res).unwrap();
+        assert_eq!(coins(100, "juno"), treasury.balances);
+        assert_eq!(coins(20, "juno"), treasury.revenue);
+        assert_eq!(
+            vec![
+                Addr::unchecked("cold_wallet"),
+                Addr::unchecked("owner_addr")
+            ],
+            treasury.wallets
+        );
+    }
+}
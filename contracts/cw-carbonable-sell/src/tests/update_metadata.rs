@@ -8,7 +8,7 @@ mod cw_carbonable {
     use cosmwasm_std::testing::{
         mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
     };
-    use cosmwasm_std::{coins, from_binary, OwnedDeps};
+    use cosmwasm_std::{coins, from_json, OwnedDeps};
     use cw_carbonable_lib::Metadata;
 
     fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
@@ -75,7 +75,7 @@ mod cw_carbonable {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(state.metadata.name.unwrap(), "Carbonable".to_string());
         assert_eq!(
             state.metadata.youtube_url.unwrap(),
@@ -107,7 +107,7 @@ mod cw_carbonable {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(state.metadata.name.unwrap(), "Carbonable".to_string());
         assert_eq!(
             state.metadata.youtube_url.unwrap(),
@@ -132,7 +132,7 @@ mod cw_carbonable {
 
         // query state
         let res = query(deps.as_ref(), mock_env(), QueryMsg::DumpState {}).unwrap();
-        let state: State = from_binary(&res).unwrap();
+        let state: State = from_json(&res).unwrap();
         assert_eq!(state.metadata.name.unwrap(), "Carbonable 2".to_string());
         assert_eq!(
             state.metadata.youtube_url.unwrap(),
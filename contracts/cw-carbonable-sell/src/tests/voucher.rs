@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, VoucherResponse};
+    use crate::state::Discount;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_json, Decimal, OwnedDeps, Uint128};
+    use sha2::{Digest, Sha256};
+
+    fn code_hash(code: &str) -> String {
+        hex::encode(Sha256::digest(code.as_bytes()))
+    }
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdateSupply {
+                reserved_supply: 0,
+                market_supply: 10,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::CreateVoucher {
+                code_hash: code_hash("EARTHDAY"),
+                discount: Discount::Percent(Decimal::percent(20)),
+                max_redemptions: 1,
+                expires: Some(mock_env().block.time.seconds() + 60),
+                allowed_address: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateVoucher {
+                code_hash: code_hash("PARTNER"),
+                discount: Discount::Fixed(Uint128::new(3)),
+                max_redemptions: 10,
+                expires: None,
+                allowed_address: Some(String::from("partner")),
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn create_voucher_unauthorized() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateVoucher {
+                code_hash: code_hash("FREE"),
+                discount: Discount::Percent(Decimal::one()),
+                max_redemptions: 100,
+                expires: None,
+                allowed_address: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn voucher_percent_single_redemption() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // 2 tokens at 10 juno - 20%
+        let info = mock_info("test", &[coin(16, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("EARTHDAY")),
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Voucher {
+                code_hash: code_hash("EARTHDAY"),
+            },
+        )
+        .unwrap();
+        let voucher: VoucherResponse = from_json(&res).unwrap();
+        assert_eq!(1, voucher.voucher.redemptions);
+        assert!(!voucher.active);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("EARTHDAY")),
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::VoucherExhausted {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn voucher_fixed_allowed_address() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(7, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("PARTNER")),
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::VoucherNotAllowed {} => {}
+            _ => unreachable!(),
+        }
+
+        // 10 juno - 3 juno
+        let info = mock_info("partner", &[coin(7, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("PARTNER")),
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn voucher_free() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CreateVoucher {
+                code_hash: code_hash("FREE"),
+                discount: Discount::Percent(Decimal::percent(100)),
+                max_redemptions: 1,
+                expires: None,
+                allowed_address: None,
+            },
+        );
+        assert!(res.is_ok());
+
+        // nothing to pay
+        let info = mock_info("test", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("FREE")),
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn voucher_expired() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(61);
+
+        let info = mock_info("test", &[coin(8, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("EARTHDAY")),
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::VoucherExpired {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn voucher_unknown() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("test", &[coin(10, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: Some(String::from("earthday")),
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::VoucherNotFound {} => {}
+            _ => unreachable!(),
+        }
+    }
+}
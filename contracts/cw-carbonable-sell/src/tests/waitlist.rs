@@ -0,0 +1,349 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, WaitlistEntryResponse};
+    use crate::state::SupplyKind;
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{attr, coin, coins, from_json, BankMsg, CosmosMsg, OwnedDeps, Response};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        helper_update_supply(deps, 2, 2);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdatePrice {
+                price: coin(10, String::from("juno")),
+            },
+        );
+        assert!(res.is_ok());
+
+        // sold out
+        let info = mock_info("early", &[coin(20, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MultiBuy {
+                quantity: 2,
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipients: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_update_supply(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        reserved_supply: u32,
+        market_supply: u32,
+    ) -> Response {
+        let info = mock_info("owner_addr", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateSupply {
+                reserved_supply,
+                market_supply,
+            },
+        )
+        .unwrap()
+    }
+
+    fn helper_join(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        buyer: &str,
+        quantity: u32,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(buyer, &[coin(10 * quantity as u128, String::from("juno"))]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::JoinWaitlist {
+                quantity,
+                max_price: None,
+            },
+        )
+    }
+
+    fn helper_fulfill(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        limit: Option<u32>,
+    ) -> Response {
+        let info = mock_info("anyone", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::FulfillWaitlist { limit },
+        )
+        .unwrap()
+    }
+
+    fn query_waitlist(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    ) -> Vec<WaitlistEntryResponse> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Waitlist {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        from_json(&res).unwrap()
+    }
+
+    #[test]
+    fn join_waitlist_when_sold_out() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("alice", &[coin(10, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::JoinWaitlist {
+                quantity: 2,
+                max_price: None,
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughMoneyForNft {} => {}
+            _ => unreachable!(),
+        }
+
+        helper_update_supply(&mut deps, 2, 3);
+
+        let res = helper_join(&mut deps, "alice", 1);
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::SupplyAvailable {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_join(&mut deps, "alice", 2);
+        assert!(res.is_ok());
+        assert_eq!(1, query_waitlist(&deps).len());
+    }
+
+    #[test]
+    fn join_waitlist_max_price() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        // the deposit covers the whole quantity
+        let info = mock_info("alice", &[coin(20, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::JoinWaitlist {
+                quantity: 2,
+                max_price: Some(coin(15, String::from("juno"))),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::PriceAboveMax { price } => {
+                assert_eq!(coin(20, String::from("juno")), price)
+            }
+            _ => unreachable!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::JoinWaitlist {
+                quantity: 2,
+                max_price: Some(coin(20, String::from("juno"))),
+            },
+        );
+        assert!(res.is_ok());
+        assert_eq!(1, query_waitlist(&deps).len());
+    }
+
+    #[test]
+    fn fulfill_waitlist_in_order() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        assert!(helper_join(&mut deps, "alice", 2).is_ok());
+        assert!(helper_join(&mut deps, "bob", 1).is_ok());
+        assert!(helper_join(&mut deps, "carol", 1).is_ok());
+
+        // alice doesn't fit, bob can't jump the queue
+        let res = helper_update_supply(&mut deps, 2, 3);
+        assert_eq!(0, res.messages.len());
+        let res = helper_fulfill(&mut deps, None);
+        assert_eq!(0, res.messages.len());
+        assert_eq!(3, query_waitlist(&deps).len());
+
+        // the new market supply goes to the waitlist
+        let res = helper_update_supply(&mut deps, 2, 6);
+        assert_eq!(4, res.messages.len());
+        assert!(res.attributes.contains(&attr("waitlist_fulfilled", "1")));
+        assert!(res.attributes.contains(&attr("waitlist_fulfilled", "2")));
+        assert!(res.attributes.contains(&attr("waitlist_fulfilled", "3")));
+        assert!(query_waitlist(&deps).is_empty());
+    }
+
+    #[test]
+    fn fulfill_waitlist_in_batches() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        for index in 0..12 {
+            assert!(helper_join(&mut deps, &format!("buyer{}", index), 1).is_ok());
+        }
+
+        // a supply update serves a bounded batch
+        let res = helper_update_supply(&mut deps, 2, 16);
+        assert_eq!(10, res.messages.len());
+        assert_eq!(2, query_waitlist(&deps).len());
+
+        let info = mock_info("bob", &[coin(10, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::WaitlistPending {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_fulfill(&mut deps, Some(1));
+        assert_eq!(1, res.messages.len());
+        assert!(res.attributes.contains(&attr("waitlist_fulfilled", "11")));
+
+        let res = helper_fulfill(&mut deps, None);
+        assert!(res.attributes.contains(&attr("waitlist_fulfilled", "12")));
+        assert!(query_waitlist(&deps).is_empty());
+    }
+
+    #[test]
+    fn waitlist_has_priority() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        assert!(helper_join(&mut deps, "alice", 1).is_ok());
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::MoveSupply {
+                from: SupplyKind::Reserved,
+                to: SupplyKind::Market,
+                amount: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(1, res.messages.len());
+        assert!(query_waitlist(&deps).is_empty());
+
+        let info = mock_info("bob", &[coin(10, String::from("juno"))]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Buy {
+                permit: None,
+                referrer: None,
+                voucher: None,
+                recipient: None,
+                product_id: None,
+                collection_id: None,
+                max_price: None,
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::NotEnoughNftLeft {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn leave_waitlist() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        helper_instantiate(&mut deps);
+
+        assert!(helper_join(&mut deps, "alice", 2).is_ok());
+
+        let info = mock_info("bob", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::LeaveWaitlist { entry_id: 1 },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::WaitlistEntryNotFound { entry_id } => assert_eq!(1, entry_id),
+            _ => unreachable!(),
+        }
+
+        let info = mock_info("alice", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::LeaveWaitlist { entry_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("alice"),
+                amount: vec![coin(20, String::from("juno"))],
+            }),
+            res.messages[0].msg
+        );
+        assert!(query_waitlist(&deps).is_empty());
+    }
+}
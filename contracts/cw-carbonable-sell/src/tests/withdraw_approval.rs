@@ -0,0 +1,288 @@
+#[cfg(test)]
+mod cw_carbonable {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::InitMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg, WithdrawProposalResponse};
+    use crate::ContractError;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, from_json, Addr, BankMsg, CosmosMsg, OwnedDeps, Response, SubMsg};
+
+    fn helper_instantiate(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = InitMsg {
+            sell_mode: true,
+            pre_sell_mode: false,
+            max_buy_at_once: 5,
+        };
+
+        let info = mock_info("owner_addr", &coins(1000, "earth"));
+
+        // we can just call .unwrap() to assert this was a success
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        for admin in ["admin_1", "admin_2"] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::AddAdmin {
+                    address: String::from(admin),
+                },
+            );
+            assert!(res.is_ok());
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateWithdrawConfig {
+                threshold: 2,
+                proposal_duration: 60,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    fn helper_propose(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Response {
+        let info = mock_info("admin_1", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Withdraw {
+                wallet: String::from("owner_addr"),
+                coin: coins(10, "juno"),
+            },
+        )
+        .unwrap()
+    }
+
+    fn helper_approve(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        approver: &str,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(approver, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ApproveWithdraw { proposal_id: 1 },
+        )
+    }
+
+    #[test]
+    fn update_withdraw_config_invalid() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("admin_1", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateWithdrawConfig {
+                threshold: 1,
+                proposal_duration: 60,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        // owner and 2 admins
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateWithdrawConfig {
+                threshold: 4,
+                proposal_duration: 60,
+            },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidWithdrawThreshold {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_needs_approvals() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let res = helper_propose(&mut deps);
+        assert!(res.messages.is_empty());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::WithdrawProposals {}).unwrap();
+        let proposals: Vec<WithdrawProposalResponse> = from_json(&res).unwrap();
+        assert_eq!(1, proposals.len());
+        assert_eq!(1, proposals[0].proposal_id);
+        assert_eq!(
+            vec![Addr::unchecked("admin_1")],
+            proposals[0].proposal.approvers
+        );
+
+        let res = helper_approve(&mut deps, "admin_1");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::WithdrawAlreadyApproved { proposal_id: 1 } => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_approve(&mut deps, "test");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        let res = helper_approve(&mut deps, "admin_2").unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("owner_addr"),
+                amount: coins(10, "juno"),
+            }))]
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::WithdrawProposals {}).unwrap();
+        let proposals: Vec<WithdrawProposalResponse> = from_json(&res).unwrap();
+        assert!(proposals.is_empty());
+    }
+
+    #[test]
+    fn withdraw_removed_admin_approval() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+        helper_propose(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RemoveAdmin {
+                address: String::from("admin_1"),
+            },
+        );
+        assert!(res.is_ok());
+
+        // approval of admin_1 no longer counts
+        let res = helper_approve(&mut deps, "admin_2").unwrap();
+        assert!(res.messages.is_empty());
+
+        let res = helper_approve(&mut deps, "owner_addr").unwrap();
+        assert_eq!(1, res.messages.len());
+    }
+
+    #[test]
+    fn remove_admin_below_threshold() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+
+        let info = mock_info("owner_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::RemoveAdmin {
+                address: String::from("admin_1"),
+            },
+        );
+        assert!(res.is_ok());
+
+        // the owner alone cannot reach a threshold of 2
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::RemoveAdmin {
+                address: String::from("admin_2"),
+            },
+        );
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::InvalidWithdrawThreshold {} => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_proposal_expired() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+        helper_propose(&mut deps);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(61);
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::WithdrawProposals {}).unwrap();
+        let proposals: Vec<WithdrawProposalResponse> = from_json(&res).unwrap();
+        assert!(proposals.is_empty());
+
+        let info = mock_info("admin_2", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::ApproveWithdraw { proposal_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::WithdrawProposalExpired { proposal_id: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_proposal_cancel() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "juno"));
+
+        helper_instantiate(&mut deps);
+        helper_propose(&mut deps);
+
+        let info = mock_info("admin_2", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CancelWithdraw { proposal_id: 1 },
+        );
+
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::Unauthorized {} => {}
+            _ => unreachable!(),
+        }
+
+        let info = mock_info("admin_1", &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::CancelWithdraw { proposal_id: 1 },
+        );
+        assert!(res.is_ok());
+
+        let res = helper_approve(&mut deps, "admin_2");
+        assert!(res.is_err());
+        match res.err().unwrap() {
+            ContractError::WithdrawProposalNotFound { proposal_id: 1 } => {}
+            _ => unreachable!(),
+        }
+    }
+}